@@ -0,0 +1,198 @@
+//! 后台 blockhash 缓存
+//!
+//! 按固定间隔轮询 RPC 的 `getLatestBlockhash`，发送路径直接读取缓存的值，无需等待 RPC。
+//! 轮询卡住（RPC 超时、连续失败）时缓存会被标记为过期。
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::json;
+use solana_sdk::hash::Hash;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::constants::{HTTP_CLIENT, JSON_RPC_URL};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::HashParam;
+
+/// 一次 `getLatestBlockhash` 的结果
+#[derive(Debug, Clone, Copy)]
+pub struct BlockhashSnapshot {
+    pub blockhash: Hash,
+    pub last_valid_block_height: u64,
+    /// 响应 context 中的 slot
+    pub slot: u64,
+    /// 本地拿到该值的时间
+    pub fetched_at: Instant,
+}
+
+/// 后台轮询的 blockhash 缓存，drop 时停止轮询
+pub struct BlockhashCache {
+    rx: watch::Receiver<Option<BlockhashSnapshot>>,
+    /// 超过该时长未刷新即视为过期
+    stale_after: Duration,
+    task: JoinHandle<()>,
+}
+
+impl BlockhashCache {
+    /// 轮询 `JSON_RPC_URL`
+    pub fn new(interval: Duration) -> Self {
+        Self::init_with(JSON_RPC_URL.clone(), interval)
+    }
+
+    /// 轮询指定 RPC，过期阈值默认为 3 个轮询间隔
+    pub fn init_with(rpc_url: impl Into<String>, interval: Duration) -> Self {
+        Self::init_with_stale_after(rpc_url, interval, interval * 3)
+    }
+
+    pub fn init_with_stale_after(
+        rpc_url: impl Into<String>,
+        interval: Duration,
+        stale_after: Duration,
+    ) -> Self {
+        let rpc_url = rpc_url.into();
+        let http_client = Arc::clone(&HTTP_CLIENT);
+        let (tx, rx) = watch::channel(None);
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match fetch_latest_blockhash(&http_client, &rpc_url).await {
+                    Ok(snapshot) => {
+                        tx.send_replace(Some(snapshot));
+                    }
+                    Err(e) => warn!("[BlockhashCache] poll {} failed: {}", rpc_url, e),
+                }
+            }
+        });
+
+        Self {
+            rx,
+            stale_after,
+            task,
+        }
+    }
+
+    /// 等待第一次轮询成功
+    pub async fn wait_ready(&self) -> BlockhashSnapshot {
+        let mut rx = self.rx.clone();
+        let snapshot = *rx
+            .wait_for(Option::is_some)
+            .await
+            .expect("blockhash poller stopped");
+        snapshot.expect("checked by wait_for")
+    }
+
+    /// 最近一次拿到的值（可能已过期，用 `is_stale` 判断）
+    pub fn latest(&self) -> Option<BlockhashSnapshot> {
+        *self.rx.borrow()
+    }
+
+    /// 缓存为空或超过 `stale_after` 未刷新
+    pub fn is_stale(&self) -> bool {
+        match self.latest() {
+            Some(snapshot) => snapshot.fetched_at.elapsed() > self.stale_after,
+            None => true,
+        }
+    }
+
+    /// 未过期时返回 `HashParam::Blockhash`，缓存为空或过期时返回 `None`
+    pub fn hash_param(&self) -> Option<HashParam> {
+        let snapshot = self.latest()?;
+        if snapshot.fetched_at.elapsed() > self.stale_after {
+            warn!(
+                "[BlockhashCache] blockhash {} is stale ({:?} old)",
+                snapshot.blockhash,
+                snapshot.fetched_at.elapsed()
+            );
+            return None;
+        }
+        Some(HashParam::Blockhash(snapshot.blockhash))
+    }
+}
+
+impl Drop for BlockhashCache {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn fetch_latest_blockhash(
+    http_client: &Client,
+    rpc_url: &str,
+) -> Result<BlockhashSnapshot, SendError> {
+    let res = http_client
+        .post(rpc_url)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getLatestBlockhash",
+            "params": [{ "commitment": "confirmed" }],
+        }))
+        .send()
+        .await;
+    let (_, body) = read_http_response(res).await?;
+    let result = parse_json_rpc_result(&body)?;
+
+    let slot = result["context"]["slot"]
+        .as_u64()
+        .ok_or_else(|| SendError::Parse(format!("missing context.slot, raw: {}", body)))?;
+    let blockhash = result["value"]["blockhash"]
+        .as_str()
+        .and_then(|s| s.parse::<Hash>().ok())
+        .ok_or_else(|| SendError::Parse(format!("invalid value.blockhash, raw: {}", body)))?;
+    let last_valid_block_height = result["value"]["lastValidBlockHeight"]
+        .as_u64()
+        .ok_or_else(|| {
+            SendError::Parse(format!("missing value.lastValidBlockHeight, raw: {}", body))
+        })?;
+    info!("[BlockhashCache] slot {} blockhash {}", slot, blockhash);
+
+    Ok(BlockhashSnapshot {
+        blockhash,
+        last_valid_block_height,
+        slot,
+        fetched_at: Instant::now(),
+    })
+}
+
+#[tokio::test]
+async fn test_blockhash_cache_with_mock_rpc() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let blockhash = Hash::new_from_array([3; 32]);
+    let down = Arc::new(AtomicBool::new(false));
+    let rpc_down = Arc::clone(&down);
+    let url = crate::test_utils::mock_json_rpc(move |_| {
+        if rpc_down.load(Ordering::SeqCst) {
+            return serde_json::Value::Null;
+        }
+        json!({
+            "context": { "slot": 100 },
+            "value": { "blockhash": blockhash.to_string(), "lastValidBlockHeight": 2_000u64 }
+        })
+    })
+    .await;
+
+    let cache = BlockhashCache::init_with_stale_after(
+        url,
+        Duration::from_millis(20),
+        Duration::from_millis(200),
+    );
+    let snapshot = cache.wait_ready().await;
+    assert_eq!(snapshot.blockhash, blockhash);
+    assert_eq!(snapshot.last_valid_block_height, 2_000);
+    assert!(matches!(cache.hash_param(), Some(HashParam::Blockhash(h)) if h == blockhash));
+
+    // RPC 不再返回有效结果后缓存过期
+    down.store(true, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert!(cache.is_stale());
+    assert!(cache.hash_param().is_none());
+    assert!(cache.latest().is_some());
+}
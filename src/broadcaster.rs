@@ -0,0 +1,470 @@
+//! 多平台并发广播
+//!
+//! 同一组指令为每个启用的平台各组装一笔交易（使用该平台自己的 tip 账户），
+//! 并发发送后汇总每个平台的签名、结果与耗时。
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use log::{error, info};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use tokio::task::JoinSet;
+
+use crate::error::{BuildError, SendError};
+use crate::platform_clients::{HashParam, PlatformName, Sender, SolTx, SubmitReceipt};
+use crate::priority_fee::AutoCuPrice;
+use crate::tip::TipStrategy;
+
+/// 广播等待模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastMode {
+    /// 等待所有平台返回
+    WaitAll,
+    /// 任一平台确认接收即返回，其余请求在后台继续执行，不计入报告
+    FirstAck,
+}
+
+/// 单个平台的发送报告
+#[derive(Debug, Clone)]
+pub struct PlatformReport {
+    pub platform: PlatformName,
+    /// 发送者的展示名（区分同一平台的不同传输，如 Astralane / AstralaneQuic）
+    pub sender: String,
+    pub sig: Signature,
+    pub result: Result<SubmitReceipt, SendError>,
+    /// 从开始序列化到拿到平台结果的耗时
+    pub latency: Duration,
+}
+
+/// 一次广播的汇总报告
+#[derive(Debug, Clone, Default)]
+pub struct BroadcastReport {
+    /// 按完成先后排列
+    pub reports: Vec<PlatformReport>,
+    /// 组装失败、未发送的平台
+    pub build_errors: Vec<(PlatformName, BuildError)>,
+}
+
+impl BroadcastReport {
+    /// 平台已接收的报告
+    pub fn accepted(&self) -> impl Iterator<Item = &PlatformReport> {
+        self.reports.iter().filter(|r| r.result.is_ok())
+    }
+
+    /// 最先确认接收的平台
+    pub fn first_ack(&self) -> Option<&PlatformReport> {
+        self.accepted().next()
+    }
+}
+
+/// 某个平台组装好的待发送交易
+type Variant = (Arc<dyn Sender>, SolTx);
+
+/// 多平台并发广播器
+pub struct Broadcaster {
+    senders: Vec<Arc<dyn Sender>>,
+    /// 各平台 tip 策略，未配置的平台使用 `default_tip`
+    tips: AHashMap<PlatformName, TipStrategy>,
+    default_tip: TipStrategy,
+    /// 调用方未给出 `cu_price` 时自动估算
+    auto_cu_price: Option<AutoCuPrice>,
+    mode: BroadcastMode,
+}
+
+impl Broadcaster {
+    pub fn init_with(senders: Vec<Arc<dyn Sender>>, mode: BroadcastMode) -> Self {
+        Self {
+            senders,
+            tips: AHashMap::new(),
+            default_tip: TipStrategy::Min,
+            auto_cu_price: None,
+            mode,
+        }
+    }
+
+    /// 设置某个平台的 tip，`0` 表示不加 tip 指令
+    pub fn set_tip(&mut self, platform: PlatformName, lamports: u64) {
+        self.tips.insert(platform, TipStrategy::Fixed(lamports));
+    }
+
+    /// 设置某个平台的 tip 策略
+    pub fn set_tip_strategy(&mut self, platform: PlatformName, tip: TipStrategy) {
+        self.tips.insert(platform, tip);
+    }
+
+    /// 设置未单独配置平台的 tip 策略，默认 `TipStrategy::Min`
+    pub fn set_default_tip(&mut self, tip: TipStrategy) {
+        self.default_tip = tip;
+    }
+
+    /// 设置自动 `cu_price` 来源，`None` 关闭
+    pub fn set_auto_cu_price(&mut self, auto_cu_price: Option<AutoCuPrice>) {
+        self.auto_cu_price = auto_cu_price;
+    }
+
+    pub fn set_mode(&mut self, mode: BroadcastMode) {
+        self.mode = mode;
+    }
+
+    pub fn senders(&self) -> &[Arc<dyn Sender>] {
+        &self.senders
+    }
+
+    /// 为每个平台组装并并发发送一笔 V0 交易
+    pub async fn broadcast(
+        &self,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        nonce: &HashParam,
+        cu: &(Option<u32>, Option<u64>),
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> BroadcastReport {
+        let cu = self.fill_cu(ixs, cu).await;
+        let (variants, report) =
+            self.build_variants(ixs, signer, nonce, &cu, address_lookup_tables, memo);
+        self.send_variants(variants, report).await
+    }
+
+    /// 只落地一次的广播：要求 `HashParam::NonceAccount`，
+    /// 发送前校验每个平台的变体都以同一个 advance-nonce 指令开头、使用同一个 nonce 值，
+    /// 因此各平台的交易签名不同，但最多只有一笔能执行。
+    pub async fn broadcast_land_once(
+        &self,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        nonce: &HashParam,
+        cu: &(Option<u32>, Option<u64>),
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> Result<BroadcastReport, BuildError> {
+        let HashParam::NonceAccount { account, hash, .. } = nonce else {
+            return Err(BuildError::Nonce(
+                "land-once broadcast requires HashParam::NonceAccount".to_string(),
+            ));
+        };
+        let cu = self.fill_cu(ixs, cu).await;
+        let (variants, report) =
+            self.build_variants(ixs, signer, nonce, &cu, address_lookup_tables, memo);
+        let (nonce_account, nonce_hash) = check_land_once(variants.iter().map(|(_, tx)| tx))?;
+        if nonce_account != *account || nonce_hash != *hash {
+            return Err(BuildError::Nonce(format!(
+                "variants use nonce {} ({}), expected {} ({})",
+                nonce_account, nonce_hash, account, hash
+            )));
+        }
+        Ok(self.send_variants(variants, report).await)
+    }
+
+    /// 配置了自动 `cu_price` 时补全 `cu`
+    async fn fill_cu(
+        &self,
+        ixs: &[Instruction],
+        cu: &(Option<u32>, Option<u64>),
+    ) -> (Option<u32>, Option<u64>) {
+        match &self.auto_cu_price {
+            Some(auto) => auto.fill(ixs, cu).await,
+            None => *cu,
+        }
+    }
+
+    /// 为每个平台组装交易，组装失败的平台记入报告的 `build_errors`
+    fn build_variants(
+        &self,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        nonce: &HashParam,
+        cu: &(Option<u32>, Option<u64>),
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> (Vec<Variant>, BroadcastReport) {
+        let mut report = BroadcastReport::default();
+        let mut variants = Vec::with_capacity(self.senders.len());
+
+        for sender in &self.senders {
+            let platform = sender.platform();
+            let tip = self.tips.get(&platform).unwrap_or(&self.default_tip);
+            match sender.build(
+                ixs,
+                signer,
+                tip,
+                nonce,
+                cu,
+                address_lookup_tables,
+                memo.clone(),
+            ) {
+                Ok(envelope) => variants.push((Arc::clone(sender), envelope.tx.tx)),
+                Err(e) => {
+                    error!("[Broadcaster] {} build failed: {}", sender, e);
+                    report.build_errors.push((platform, e));
+                }
+            }
+        }
+        (variants, report)
+    }
+
+    /// 并发发送所有变体，按 `mode` 决定何时返回
+    async fn send_variants(
+        &self,
+        variants: Vec<Variant>,
+        mut report: BroadcastReport,
+    ) -> BroadcastReport {
+        let mut tasks = JoinSet::new();
+        for (sender, tx) in variants {
+            tasks.spawn(async move {
+                let started = Instant::now();
+                let result = match tx.to_bytes() {
+                    Ok(bytes) => sender.send_tx_raw(&bytes).await,
+                    Err(e) => Err(e),
+                };
+                PlatformReport {
+                    platform: sender.platform(),
+                    sender: sender.to_string(),
+                    sig: tx.sig(),
+                    result,
+                    latency: started.elapsed(),
+                }
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            let platform_report = match joined {
+                Ok(r) => r,
+                Err(e) => {
+                    error!("[Broadcaster] task join error: {}", e);
+                    continue;
+                }
+            };
+            match &platform_report.result {
+                Ok(_) => info!(
+                    "[Broadcaster] {} accepted {} in {:?}",
+                    platform_report.sender, platform_report.sig, platform_report.latency
+                ),
+                Err(e) => error!("[Broadcaster] {} failed: {}", platform_report.sender, e),
+            }
+            let acked = platform_report.result.is_ok();
+            report.reports.push(platform_report);
+            if acked && self.mode == BroadcastMode::FirstAck {
+                tasks.detach_all();
+                break;
+            }
+        }
+
+        report
+    }
+}
+
+/// 校验一组变体满足"最多落地一次"：每笔交易的第一条指令都是 advance-nonce，
+/// 且 nonce 账户与 nonce 值完全一致。返回共用的 `(nonce account, nonce)`。
+///
+/// 混用 blockhash 与 nonce 的集合、或不同 nonce 账户的集合都会被拒绝。
+pub fn check_land_once<'a>(
+    txs: impl IntoIterator<Item = &'a SolTx>,
+) -> Result<(Pubkey, Hash), BuildError> {
+    let mut shared: Option<(Pubkey, Hash)> = None;
+    for tx in txs {
+        let Some((account, _authority)) = tx.advance_nonce() else {
+            return Err(BuildError::Nonce(format!(
+                "{} does not start with an advance-nonce instruction",
+                tx.sig()
+            )));
+        };
+        let current = (account, tx.recent_blockhash());
+        match shared {
+            None => shared = Some(current),
+            Some(first) if first != current => {
+                return Err(BuildError::Nonce(format!(
+                    "{} uses nonce {} ({}), other variants use {} ({})",
+                    tx.sig(),
+                    current.0,
+                    current.1,
+                    first.0,
+                    first.1
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    shared.ok_or_else(|| BuildError::Nonce("no transaction variants to check".to_string()))
+}
+
+#[tokio::test]
+async fn test_broadcast_modes() {
+    use crate::platform_clients::{BuildTx, BuildV0Tx, SendTxEncoded};
+    use solana_sdk::signer::Signer;
+
+    struct Mock {
+        platform: PlatformName,
+        delay: Duration,
+        accept: bool,
+    }
+    impl std::fmt::Display for Mock {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "Mock({})", self.platform)
+        }
+    }
+    impl BuildTx for Mock {
+        fn get_tip_address(&self) -> Pubkey {
+            Pubkey::new_from_array([7; 32])
+        }
+        fn get_min_tip_amount(&self) -> u64 {
+            1_000
+        }
+        fn platform(&self) -> PlatformName {
+            self.platform
+        }
+        fn tip_recvs(&self) -> Vec<Pubkey> {
+            vec![self.get_tip_address()]
+        }
+    }
+    impl BuildV0Tx for Mock {}
+    #[async_trait::async_trait]
+    impl SendTxEncoded for Mock {
+        async fn send_tx_encoded(&self, _tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+            tokio::time::sleep(self.delay).await;
+            if !self.accept {
+                return Err(SendError::RateLimited("mock".to_string()));
+            }
+            Ok(SubmitReceipt {
+                platform: self.platform,
+                endpoint: "mock".to_string(),
+                latency: self.delay,
+                http_status: Some(200),
+                server_id: None,
+                raw: None,
+            })
+        }
+    }
+
+    let senders: Vec<Arc<dyn Sender>> = vec![
+        Arc::new(Mock {
+            platform: PlatformName::Jito,
+            delay: Duration::from_millis(10),
+            accept: false,
+        }),
+        Arc::new(Mock {
+            platform: PlatformName::Helius,
+            delay: Duration::from_millis(20),
+            accept: true,
+        }),
+        Arc::new(Mock {
+            platform: PlatformName::Zeroslot,
+            delay: Duration::from_millis(500),
+            accept: true,
+        }),
+    ];
+    let mut broadcaster = Broadcaster::init_with(senders, BroadcastMode::WaitAll);
+    broadcaster.set_tip(PlatformName::Helius, 0);
+
+    let signer = Arc::new(Keypair::new());
+    let ix =
+        solana_system_interface::instruction::transfer(&signer.pubkey(), &Pubkey::new_unique(), 1);
+    let nonce = HashParam::Blockhash(Hash::default());
+
+    let report = broadcaster
+        .broadcast(
+            std::slice::from_ref(&ix),
+            &signer,
+            &nonce,
+            &(None, None),
+            &[],
+            None,
+        )
+        .await;
+    assert_eq!(report.reports.len(), 3);
+    assert_eq!(report.accepted().count(), 2);
+    assert_eq!(report.first_ack().unwrap().platform, PlatformName::Helius);
+    // 不同平台的 tip 不同，签名也不同
+    assert_ne!(report.reports[0].sig, report.reports[1].sig);
+
+    broadcaster.set_mode(BroadcastMode::FirstAck);
+    let report = broadcaster
+        .broadcast(
+            std::slice::from_ref(&ix),
+            &signer,
+            &nonce,
+            &(None, None),
+            &[],
+            None,
+        )
+        .await;
+    assert_eq!(report.reports.len(), 2);
+    assert_eq!(report.first_ack().unwrap().platform, PlatformName::Helius);
+
+    // land-once 模式拒绝 blockhash
+    assert!(matches!(
+        broadcaster
+            .broadcast_land_once(
+                std::slice::from_ref(&ix),
+                &signer,
+                &nonce,
+                &(None, None),
+                &[],
+                None
+            )
+            .await,
+        Err(BuildError::Nonce(_))
+    ));
+
+    broadcaster.set_mode(BroadcastMode::WaitAll);
+    let durable = HashParam::NonceAccount {
+        account: Pubkey::new_unique(),
+        authority: signer.pubkey(),
+        hash: Hash::new_from_array([9; 32]),
+    };
+    let report = broadcaster
+        .broadcast_land_once(
+            std::slice::from_ref(&ix),
+            &signer,
+            &durable,
+            &(None, None),
+            &[],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.reports.len(), 3);
+
+    // 混用 blockhash / nonce 的变体集合被拒绝
+    let mock = Mock {
+        platform: PlatformName::Jito,
+        delay: Duration::ZERO,
+        accept: true,
+    };
+    let with_nonce = mock
+        .build(
+            std::slice::from_ref(&ix),
+            &signer,
+            &TipStrategy::Min,
+            &durable,
+            &(None, None),
+            &[],
+            None,
+        )
+        .unwrap()
+        .tx
+        .tx;
+    let with_blockhash = mock
+        .build(
+            &[ix],
+            &signer,
+            &TipStrategy::Min,
+            &nonce,
+            &(None, None),
+            &[],
+            None,
+        )
+        .unwrap()
+        .tx
+        .tx;
+    assert!(check_land_once([&with_nonce]).is_ok());
+    assert!(matches!(
+        check_land_once([&with_nonce, &with_blockhash]),
+        Err(BuildError::Nonce(_))
+    ));
+}
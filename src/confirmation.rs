@@ -0,0 +1,375 @@
+//! 交易落地跟踪
+//!
+//! 批量轮询 `getSignatureStatuses`，记录每个签名的状态、落地 slot 与发送到落地的耗时；
+//! 同一意图的多平台变体可按组查询，得到实际落地的平台。
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::json;
+use solana_sdk::signature::Signature;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::broadcaster::BroadcastReport;
+use crate::constants::{HTTP_CLIENT, JSON_RPC_URL};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::PlatformName;
+
+/// `getSignatureStatuses` 单次最多 256 个签名
+const MAX_SIGNATURES_PER_REQUEST: usize = 256;
+
+/// 交易状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    /// 尚未在链上看到
+    Pending,
+    Processed,
+    Confirmed,
+    Finalized,
+    /// 已落地但执行失败，内容为 RPC 返回的错误
+    Failed(String),
+    /// blockhash 过期或超过跟踪时长仍未落地
+    Expired,
+}
+
+impl TxStatus {
+    /// 已在链上（包括执行失败）
+    pub fn is_landed(&self) -> bool {
+        matches!(
+            self,
+            TxStatus::Processed | TxStatus::Confirmed | TxStatus::Finalized | TxStatus::Failed(_)
+        )
+    }
+
+    /// 不会再变化的状态
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TxStatus::Finalized | TxStatus::Failed(_) | TxStatus::Expired
+        )
+    }
+}
+
+/// 单个签名的跟踪结果
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub sig: Signature,
+    pub platform: Option<PlatformName>,
+    pub status: TxStatus,
+    /// 落地 slot
+    pub slot: Option<u64>,
+    /// 从开始跟踪到首次在链上看到的耗时，精度受轮询间隔限制
+    pub land_delay: Option<Duration>,
+}
+
+struct Tracked {
+    confirmation: Confirmation,
+    sent_at: Instant,
+    last_valid_block_height: Option<u64>,
+}
+
+/// 签名状态跟踪器
+pub struct ConfirmationTracker {
+    rpc_url: String,
+    http_client: Arc<Client>,
+    /// 没有 `last_valid_block_height` 的签名（如 durable nonce 交易）超过该时长视为过期
+    max_age: Duration,
+    entries: Mutex<AHashMap<Signature, Tracked>>,
+    updates: broadcast::Sender<Confirmation>,
+}
+
+impl ConfirmationTracker {
+    pub fn new() -> Self {
+        Self::init_with(JSON_RPC_URL.clone(), Duration::from_secs(90))
+    }
+
+    pub fn init_with(rpc_url: impl Into<String>, max_age: Duration) -> Self {
+        let (updates, _) = broadcast::channel(1024);
+        Self {
+            rpc_url: rpc_url.into(),
+            http_client: Arc::clone(&HTTP_CLIENT),
+            max_age,
+            entries: Mutex::new(AHashMap::new()),
+            updates,
+        }
+    }
+
+    /// 订阅状态变化
+    pub fn subscribe(&self) -> broadcast::Receiver<Confirmation> {
+        self.updates.subscribe()
+    }
+
+    /// 开始跟踪一个签名，以当前时间作为发送时间
+    pub fn track(
+        &self,
+        sig: Signature,
+        platform: Option<PlatformName>,
+        last_valid_block_height: Option<u64>,
+    ) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.entry(sig).or_insert_with(|| Tracked {
+            confirmation: Confirmation {
+                sig,
+                platform,
+                status: TxStatus::Pending,
+                slot: None,
+                land_delay: None,
+            },
+            sent_at: Instant::now(),
+            last_valid_block_height,
+        });
+    }
+
+    /// 跟踪一次广播中平台已接收的所有变体，返回这些签名，可传给 `winner`
+    pub fn track_broadcast(
+        &self,
+        report: &BroadcastReport,
+        last_valid_block_height: Option<u64>,
+    ) -> Vec<Signature> {
+        report
+            .accepted()
+            .map(|r| {
+                self.track(r.sig, Some(r.platform), last_valid_block_height);
+                r.sig
+            })
+            .collect()
+    }
+
+    pub fn status(&self, sig: &Signature) -> Option<Confirmation> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(sig).map(|t| t.confirmation.clone())
+    }
+
+    /// 一组变体中已落地的那一笔（durable nonce 保证最多一笔落地）
+    pub fn winner(&self, sigs: &[Signature]) -> Option<Confirmation> {
+        let entries = self.entries.lock().unwrap();
+        sigs.iter()
+            .filter_map(|sig| entries.get(sig))
+            .filter(|t| t.confirmation.status.is_landed())
+            .min_by_key(|t| t.confirmation.slot)
+            .map(|t| t.confirmation.clone())
+    }
+
+    /// 停止跟踪并返回最后的状态
+    pub fn remove(&self, sig: &Signature) -> Option<Confirmation> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(sig).map(|t| t.confirmation)
+    }
+
+    /// 跟踪中且未到终态的签名数
+    pub fn pending(&self) -> usize {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .values()
+            .filter(|t| !t.confirmation.status.is_terminal())
+            .count()
+    }
+
+    /// 轮询一次，返回状态有变化的签名
+    pub async fn poll_once(&self) -> Result<Vec<Confirmation>, SendError> {
+        let (sigs, need_block_height) = {
+            let entries = self.entries.lock().unwrap();
+            let active: Vec<_> = entries
+                .values()
+                .filter(|t| !t.confirmation.status.is_terminal())
+                .collect();
+            let need_block_height = active.iter().any(|t| t.last_valid_block_height.is_some());
+            let sigs: Vec<Signature> = active.iter().map(|t| t.confirmation.sig).collect();
+            (sigs, need_block_height)
+        };
+        if sigs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 先取 block height 再查状态，避免把查询期间刚落地的交易误判为过期
+        let block_height = if need_block_height {
+            Some(self.get_block_height().await?)
+        } else {
+            None
+        };
+        let mut statuses = Vec::with_capacity(sigs.len());
+        for chunk in sigs.chunks(MAX_SIGNATURES_PER_REQUEST) {
+            statuses.extend(self.get_signature_statuses(chunk).await?);
+        }
+
+        let mut changed = Vec::new();
+        let mut entries = self.entries.lock().unwrap();
+        for (sig, status) in sigs.iter().zip(statuses) {
+            let Some(tracked) = entries.get_mut(sig) else {
+                continue;
+            };
+            let confirmation = &mut tracked.confirmation;
+            let (new_status, slot) = match status {
+                Some(landed) => landed,
+                None => {
+                    let expired = match (tracked.last_valid_block_height, block_height) {
+                        (Some(last_valid), Some(current)) => current > last_valid,
+                        _ => tracked.sent_at.elapsed() > self.max_age,
+                    };
+                    if !expired {
+                        continue;
+                    }
+                    (TxStatus::Expired, None)
+                }
+            };
+            if new_status == confirmation.status {
+                continue;
+            }
+            if new_status.is_landed() && confirmation.land_delay.is_none() {
+                confirmation.land_delay = Some(tracked.sent_at.elapsed());
+            }
+            confirmation.status = new_status;
+            confirmation.slot = slot.or(confirmation.slot);
+            match &confirmation.status {
+                TxStatus::Expired => warn!("[ConfirmationTracker] {} expired", sig),
+                status => info!(
+                    "[ConfirmationTracker] {} {:?} at slot {:?} via {:?} after {:?}",
+                    sig, status, confirmation.slot, confirmation.platform, confirmation.land_delay
+                ),
+            }
+            let _ = self.updates.send(confirmation.clone());
+            changed.push(confirmation.clone());
+        }
+        Ok(changed)
+    }
+
+    /// 后台按间隔轮询，状态变化通过 `subscribe` 获取
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.poll_once().await {
+                    warn!("[ConfirmationTracker] poll failed: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn rpc_call(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, SendError> {
+        let res = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await;
+        let (_, body) = read_http_response(res).await?;
+        parse_json_rpc_result(&body)
+    }
+
+    async fn get_block_height(&self) -> Result<u64, SendError> {
+        let result = self
+            .rpc_call("getBlockHeight", json!([{ "commitment": "confirmed" }]))
+            .await?;
+        result
+            .as_u64()
+            .ok_or_else(|| SendError::Parse(format!("invalid block height: {}", result)))
+    }
+
+    /// 返回与 `sigs` 一一对应的 `(状态, slot)`，未找到为 `None`
+    async fn get_signature_statuses(
+        &self,
+        sigs: &[Signature],
+    ) -> Result<Vec<Option<(TxStatus, Option<u64>)>>, SendError> {
+        let sigs: Vec<String> = sigs.iter().map(|s| s.to_string()).collect();
+        let result = self
+            .rpc_call(
+                "getSignatureStatuses",
+                json!([sigs, { "searchTransactionHistory": false }]),
+            )
+            .await?;
+        let values = result["value"]
+            .as_array()
+            .filter(|v| v.len() == sigs.len())
+            .ok_or_else(|| SendError::Parse(format!("invalid signature statuses: {}", result)))?;
+
+        Ok(values
+            .iter()
+            .map(|value| {
+                if value.is_null() {
+                    return None;
+                }
+                let slot = value["slot"].as_u64();
+                let err = &value["err"];
+                let status = if !err.is_null() {
+                    TxStatus::Failed(err.to_string())
+                } else {
+                    match value["confirmationStatus"].as_str() {
+                        Some("finalized") => TxStatus::Finalized,
+                        Some("confirmed") => TxStatus::Confirmed,
+                        _ => TxStatus::Processed,
+                    }
+                };
+                Some((status, slot))
+            })
+            .collect())
+    }
+}
+
+impl Default for ConfirmationTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tokio::test]
+async fn test_confirmation_tracker_with_mock_rpc() {
+    let landed = Signature::from([1u8; 64]);
+    let failed = Signature::from([2u8; 64]);
+    let expiring = Signature::from([3u8; 64]);
+
+    let url = crate::test_utils::mock_json_rpc(move |req| {
+        if req["method"] == "getBlockHeight" {
+            return json!(1_000);
+        }
+        let value: Vec<_> = req["params"][0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| match s.as_str().unwrap() {
+                s if s == landed.to_string() => json!({
+                    "slot": 42, "confirmations": 1, "err": null, "confirmationStatus": "confirmed"
+                }),
+                s if s == failed.to_string() => json!({
+                    "slot": 43, "confirmations": null, "err": { "InstructionError": [0, "Custom"] }, "confirmationStatus": "finalized"
+                }),
+                _ => serde_json::Value::Null,
+            })
+            .collect();
+        json!({ "context": { "slot": 50 }, "value": value })
+    })
+    .await;
+
+    let tracker = ConfirmationTracker::init_with(url, Duration::from_secs(60));
+    tracker.track(landed, Some(PlatformName::Jito), Some(2_000));
+    tracker.track(failed, Some(PlatformName::Helius), None);
+    tracker.track(expiring, Some(PlatformName::Zeroslot), Some(900));
+
+    assert_eq!(tracker.poll_once().await.unwrap().len(), 3);
+    assert_eq!(tracker.status(&landed).unwrap().status, TxStatus::Confirmed);
+    assert!(matches!(
+        tracker.status(&failed).unwrap().status,
+        TxStatus::Failed(_)
+    ));
+    assert_eq!(tracker.status(&expiring).unwrap().status, TxStatus::Expired);
+
+    let winner = tracker.winner(&[expiring, landed]).unwrap();
+    assert_eq!(winner.platform, Some(PlatformName::Jito));
+    // 仅剩 landed 未到终态，状态不变时不再上报
+    assert!(tracker.poll_once().await.unwrap().is_empty());
+    assert_eq!(tracker.pending(), 1);
+}
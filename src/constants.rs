@@ -6,6 +6,7 @@ use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
 use std::env;
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 
 pub mod api_config {
     pub const BLOCKRAZOR_KEY: &str = "";
@@ -20,13 +21,14 @@ pub static HTTP_CLIENT: LazyLock<Arc<Client>> = LazyLock::new(|| {
     )
 });
 
-pub static JSON_RPC_CLIENT: LazyLock<RpcClient> = LazyLock::new(|| {
-    RpcClient::new(
-        std::env::var("JSON_RPC_URL")
-            .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string()),
-    )
+pub static JSON_RPC_URL: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("JSON_RPC_URL")
+        .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
 });
 
+pub static JSON_RPC_CLIENT: LazyLock<RpcClient> =
+    LazyLock::new(|| RpcClient::new(JSON_RPC_URL.clone()));
+
 pub static REGION: LazyLock<Region> = LazyLock::new(|| {
     let region_str = std::env::var("REGION").unwrap_or_else(|_| "Frankfurt".to_string());
     Region::from(region_str)
@@ -34,3 +36,12 @@ pub static REGION: LazyLock<Region> = LazyLock::new(|| {
 
 pub static MEMO_PROGRAM: LazyLock<solana_sdk::pubkey::Pubkey> =
     LazyLock::new(|| pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"));
+
+/// 单笔交易序列化后的最大字节数（与 Solana PACKET_DATA_SIZE 一致）
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// 单个 bundle 最多包含的交易数（block engine 限制）
+pub const MAX_BUNDLE_TXS: usize = 5;
+
+/// blockhash 有效时长的保守估计（150 个区块约 60s，另留余量）
+pub const BLOCKHASH_TTL: Duration = Duration::from_secs(90);
@@ -0,0 +1,387 @@
+//! 交易组装 / 发送阶段的错误类型
+//!
+//! 调用方可按变体决定重试、切换平台或直接放弃，不再需要匹配错误字符串。
+
+use std::fmt;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::TransactionError;
+
+// ── SendError ────────────────────────────────────────────────────────────────
+
+/// 发送阶段错误，所有平台客户端统一映射到此枚举
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendError {
+    /// 传输层失败（DNS / TCP / TLS / QUIC / gRPC 连接等）
+    Transport(String),
+    /// 请求超时
+    Timeout(String),
+    /// 鉴权失败（HTTP 401/403、gRPC Unauthenticated、API key 无效等）
+    Auth(String),
+    /// 被限流（HTTP 429、gRPC ResourceExhausted）
+    RateLimited(String),
+    /// 平台明确拒绝，`code` 为平台返回的错误码（JSON-RPC code / gRPC code）
+    Rejected { code: Option<i64>, message: String },
+    /// 其他非 2xx HTTP 响应
+    Http { status: u16, body: String },
+    /// 响应解析失败
+    Parse(String),
+    /// 交易序列化 / 编码失败
+    Serialize(String),
+    /// 序列化后的交易超过大小上限
+    Oversize { size: usize, max: usize },
+    /// bundle 交易数不在 `min..=max` 范围内
+    BundleSize {
+        count: usize,
+        min: usize,
+        max: usize,
+    },
+    /// 发送前模拟失败，交易未发出；`err` 为解码后的执行错误，无法解码时为 `None`
+    SimulationFailed {
+        err: Option<TransactionError>,
+        message: String,
+        logs: Vec<String>,
+    },
+}
+
+impl SendError {
+    /// 是否值得对同一平台重试（传输错误、超时、限流、5xx）
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SendError::Transport(_) | SendError::Timeout(_) | SendError::RateLimited(_) => true,
+            SendError::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// 按 HTTP 状态码归类，2xx 返回 `None`。
+    ///
+    /// 非 2xx 但 body 是 JSON-RPC error 时映射为 `Rejected`，保留平台错误码。
+    pub fn from_http_status(status: u16, body: &str) -> Option<SendError> {
+        match status {
+            200..=299 => None,
+            401 | 403 => Some(SendError::Auth(format!("http {}: {}", status, body))),
+            429 => Some(SendError::RateLimited(format!("http 429: {}", body))),
+            _ => {
+                let rpc_err = serde_json::from_str::<serde_json::Value>(body)
+                    .ok()
+                    .and_then(|v| v.get("error").filter(|e| !e.is_null()).cloned());
+                match rpc_err {
+                    Some(err) => Some(SendError::from_rpc_error(&err)),
+                    None => Some(SendError::Http {
+                        status,
+                        body: body.to_string(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// 将 JSON-RPC `error` 字段映射为 `Rejected`
+    pub fn from_rpc_error(err: &serde_json::Value) -> SendError {
+        let code = err.get("code").and_then(|c| c.as_i64());
+        let message = err
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| err.to_string());
+        SendError::Rejected { code, message }
+    }
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendError::Transport(msg) => write!(f, "transport error: {}", msg),
+            SendError::Timeout(msg) => write!(f, "timeout: {}", msg),
+            SendError::Auth(msg) => write!(f, "auth error: {}", msg),
+            SendError::RateLimited(msg) => write!(f, "rate limited: {}", msg),
+            SendError::Rejected {
+                code: Some(code),
+                message,
+            } => write!(f, "rejected (code {}): {}", code, message),
+            SendError::Rejected {
+                code: None,
+                message,
+            } => write!(f, "rejected: {}", message),
+            SendError::Http { status, body } => write!(f, "http {}: {}", status, body),
+            SendError::Parse(msg) => write!(f, "response parse error: {}", msg),
+            SendError::Serialize(msg) => write!(f, "serialize error: {}", msg),
+            SendError::Oversize { size, max } => {
+                write!(f, "transaction too large: {} bytes (max {})", size, max)
+            }
+            SendError::BundleSize { count, min, max } => {
+                write!(
+                    f,
+                    "bundle must contain {}..={} transactions, got {}",
+                    min, max, count
+                )
+            }
+            SendError::SimulationFailed { message, logs, .. } => write!(
+                f,
+                "preflight simulation failed: {} ({} log lines)",
+                message,
+                logs.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SendError {}
+
+impl From<reqwest::Error> for SendError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            SendError::Timeout(e.to_string())
+        } else if let Some(status) = e.status() {
+            SendError::from_http_status(status.as_u16(), &e.to_string())
+                .unwrap_or_else(|| SendError::Transport(e.to_string()))
+        } else {
+            SendError::Transport(e.to_string())
+        }
+    }
+}
+
+impl From<tonic::Status> for SendError {
+    fn from(status: tonic::Status) -> Self {
+        use tonic::Code;
+        let message = status.message().to_string();
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => SendError::Auth(message),
+            Code::ResourceExhausted => SendError::RateLimited(message),
+            Code::DeadlineExceeded => SendError::Timeout(message),
+            Code::Unavailable | Code::Cancelled | Code::Aborted => SendError::Transport(message),
+            code => SendError::Rejected {
+                code: Some(code as i64),
+                message,
+            },
+        }
+    }
+}
+
+impl From<tonic::transport::Error> for SendError {
+    fn from(e: tonic::transport::Error) -> Self {
+        SendError::Transport(e.to_string())
+    }
+}
+
+impl From<solana_client::client_error::ClientError> for SendError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        use solana_client::client_error::ClientErrorKind;
+        use solana_client::rpc_request::RpcError;
+        match e.kind() {
+            ClientErrorKind::Reqwest(re) if re.is_timeout() => SendError::Timeout(e.to_string()),
+            ClientErrorKind::Reqwest(re) => match re.status() {
+                Some(status) => SendError::from_http_status(status.as_u16(), &e.to_string())
+                    .unwrap_or_else(|| SendError::Transport(e.to_string())),
+                None => SendError::Transport(e.to_string()),
+            },
+            ClientErrorKind::Io(_) => SendError::Transport(e.to_string()),
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+                SendError::Rejected {
+                    code: Some(*code),
+                    message: message.clone(),
+                }
+            }
+            ClientErrorKind::RpcError(RpcError::ParseError(msg)) => SendError::Parse(msg.clone()),
+            ClientErrorKind::SerdeJson(_) => SendError::Parse(e.to_string()),
+            ClientErrorKind::SigningError(_) => SendError::Serialize(e.to_string()),
+            _ => SendError::Rejected {
+                code: None,
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+/// 读取 HTTP 响应 body：传输错误与非 2xx 状态统一映射为 `SendError`，
+/// 成功时返回状态码与原始 body。
+pub(crate) async fn read_http_response(
+    res: Result<reqwest::Response, reqwest::Error>,
+) -> Result<(u16, String), SendError> {
+    let resp = res?;
+    let status = resp.status().as_u16();
+    let body = resp.text().await.map_err(|e| {
+        if e.is_timeout() {
+            SendError::Timeout(format!("response text error: {}", e))
+        } else {
+            SendError::Transport(format!("response text error: {}", e))
+        }
+    })?;
+    match SendError::from_http_status(status, &body) {
+        Some(err) => Err(err),
+        None => Ok((status, body)),
+    }
+}
+
+/// 解析 JSON-RPC 响应：`error` 字段映射为 `Rejected`，成功时返回 `result`
+pub(crate) fn parse_json_rpc_result(body: &str) -> Result<serde_json::Value, SendError> {
+    let value: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| SendError::Parse(format!("{}, raw: {}", e, body)))?;
+    if let Some(err) = value.get("error").filter(|e| !e.is_null()) {
+        return Err(SendError::from_rpc_error(err));
+    }
+    match value.get("result") {
+        Some(result) if !result.is_null() => Ok(result.clone()),
+        _ => Err(SendError::Parse(format!("missing result, raw: {}", body))),
+    }
+}
+
+// ── BuildError ───────────────────────────────────────────────────────────────
+
+/// 交易组装阶段错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// V0 message 编译失败（账户过多、lookup table 冲突等）
+    Compile(String),
+    /// 签名失败（签名者缺失或不匹配）
+    Sign(String),
+    /// durable nonce 校验失败（缺少 advance-nonce 指令、nonce 不一致或混用 blockhash）
+    Nonce(String),
+    /// 自动 CU 模拟失败（RPC 错误或交易执行出错），附带模拟日志
+    Simulation { message: String, logs: Vec<String> },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Compile(msg) => write!(f, "compile message error: {}", msg),
+            BuildError::Sign(msg) => write!(f, "sign transaction error: {}", msg),
+            BuildError::Nonce(msg) => write!(f, "durable nonce error: {}", msg),
+            BuildError::Simulation { message, logs } => write!(
+                f,
+                "simulation error: {} ({} log lines)",
+                message,
+                logs.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+// ── NonceError ───────────────────────────────────────────────────────────────
+
+/// durable nonce 账户读取 / 管理错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonceError {
+    /// RPC 请求或交易提交失败
+    Rpc(SendError),
+    /// 账户不存在
+    AccountNotFound(Pubkey),
+    /// 账户 owner 不是 System Program
+    WrongOwner { account: Pubkey, owner: Pubkey },
+    /// 账户数据无法解析为 nonce 状态
+    InvalidData { account: Pubkey, message: String },
+    /// nonce 账户尚未初始化
+    Uninitialized(Pubkey),
+    /// 链上 authority 与期望不一致
+    AuthorityMismatch {
+        account: Pubkey,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// 账户不在 nonce 池中
+    NotInPool(Pubkey),
+    /// nonce 账户正被租用，不能回收
+    Leased(Pubkey),
+    /// 上次租约后 nonce 仍未推进，在途交易可能还会消耗它
+    NotAdvanced(Pubkey),
+}
+
+impl fmt::Display for NonceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonceError::Rpc(e) => write!(f, "nonce rpc error: {}", e),
+            NonceError::AccountNotFound(account) => {
+                write!(f, "nonce account {} not found", account)
+            }
+            NonceError::WrongOwner { account, owner } => write!(
+                f,
+                "account {} is owned by {}, not the system program",
+                account, owner
+            ),
+            NonceError::InvalidData { account, message } => {
+                write!(f, "account {} is not a nonce account: {}", account, message)
+            }
+            NonceError::Uninitialized(account) => {
+                write!(f, "nonce account {} is not initialized", account)
+            }
+            NonceError::AuthorityMismatch {
+                account,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "nonce account {} authority is {}, expected {}",
+                account, actual, expected
+            ),
+            NonceError::NotInPool(account) => {
+                write!(f, "nonce account {} is not in the pool", account)
+            }
+            NonceError::Leased(account) => write!(f, "nonce account {} is leased", account),
+            NonceError::NotAdvanced(account) => {
+                write!(
+                    f,
+                    "nonce account {} has not advanced since last lease",
+                    account
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NonceError {}
+
+impl From<SendError> for NonceError {
+    fn from(e: SendError) -> Self {
+        NonceError::Rpc(e)
+    }
+}
+
+impl From<solana_client::client_error::ClientError> for NonceError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        NonceError::Rpc(SendError::from(e))
+    }
+}
+
+#[test]
+fn test_send_error_classification() {
+    assert_eq!(SendError::from_http_status(200, "ok"), None);
+    assert!(matches!(
+        SendError::from_http_status(429, "slow down"),
+        Some(SendError::RateLimited(_))
+    ));
+    assert!(matches!(
+        SendError::from_http_status(401, ""),
+        Some(SendError::Auth(_))
+    ));
+    assert_eq!(
+        SendError::from_http_status(
+            400,
+            r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"bad params"},"id":1}"#
+        ),
+        Some(SendError::Rejected {
+            code: Some(-32602),
+            message: "bad params".to_string(),
+        })
+    );
+    assert!(SendError::from_http_status(503, "").unwrap().is_retryable());
+    assert!(!SendError::from_http_status(404, "").unwrap().is_retryable());
+
+    assert_eq!(
+        parse_json_rpc_result(r#"{"jsonrpc":"2.0","result":"abc","id":1}"#).unwrap(),
+        serde_json::json!("abc")
+    );
+    assert!(matches!(
+        parse_json_rpc_result(r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"x"},"id":1}"#),
+        Err(SendError::Rejected {
+            code: Some(-32000),
+            ..
+        })
+    ));
+    assert!(matches!(
+        parse_json_rpc_result("<html>"),
+        Err(SendError::Parse(_))
+    ));
+}
@@ -1,2 +1,16 @@
+pub mod blockhash_cache;
+pub mod broadcaster;
+pub mod confirmation;
 pub mod constants;
+pub mod error;
+pub mod nonce;
 pub mod platform_clients;
+pub mod priority_fee;
+pub mod rate_limit;
+pub mod retry;
+pub mod simulation;
+pub mod tip;
+pub mod tip_accounts;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,552 @@
+//! durable nonce 账户池
+//!
+//! 每次发送从池中租用一个空闲的 nonce 账户，租约释放后该账户的 nonce 值会在下次租用前
+//! 从链上重新读取，保证并发策略不会共用同一个 nonce 账户。
+
+use std::sync::{Arc, Mutex};
+
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_nonce::state::State;
+use solana_nonce::versions::Versions;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use solana_system_interface::instruction::{
+    advance_nonce_account, create_nonce_account, withdraw_nonce_account,
+};
+use tokio::sync::Notify;
+
+use crate::error::NonceError;
+use crate::platform_clients::HashParam;
+
+/// 解析 nonce 账户，返回 `(authority, 当前 nonce 值)`
+pub fn parse_nonce_account(
+    address: &Pubkey,
+    account: &Account,
+) -> Result<(Pubkey, Hash), NonceError> {
+    if account.owner != solana_system_interface::program::ID {
+        return Err(NonceError::WrongOwner {
+            account: *address,
+            owner: account.owner,
+        });
+    }
+    let versions: Versions =
+        bincode::deserialize(&account.data).map_err(|e| NonceError::InvalidData {
+            account: *address,
+            message: e.to_string(),
+        })?;
+    match versions.state() {
+        State::Uninitialized => Err(NonceError::Uninitialized(*address)),
+        State::Initialized(data) => Ok((data.authority, data.blockhash())),
+    }
+}
+
+/// 从链上读取 nonce 账户，返回 `(authority, 当前 nonce 值)`
+pub async fn fetch_nonce_account(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<(Pubkey, Hash), NonceError> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())
+        .await?
+        .value
+        .ok_or(NonceError::AccountNotFound(*address))?;
+    parse_nonce_account(address, &account)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NonceSlot {
+    account: Pubkey,
+    hash: Hash,
+    leased: bool,
+    /// 上一次租约可能已推进 nonce，下次租用前需从链上刷新
+    dirty: bool,
+}
+
+struct PoolInner {
+    rpc: Arc<RpcClient>,
+    authority: Arc<Keypair>,
+    slots: Mutex<Vec<NonceSlot>>,
+    released: Notify,
+}
+
+impl PoolInner {
+    fn release(&self, account: &Pubkey, consumed: bool) {
+        let mut slots = self.slots.lock().unwrap();
+        if let Some(slot) = slots.iter_mut().find(|s| s.account == *account) {
+            slot.leased = false;
+            slot.dirty |= consumed;
+        }
+        drop(slots);
+        self.released.notify_one();
+    }
+
+    /// 占用一个空闲账户，跳过 `skip` 中的账户
+    fn take_free(&self, skip: &[Pubkey]) -> Option<NonceSlot> {
+        let mut slots = self.slots.lock().unwrap();
+        let slot = slots
+            .iter_mut()
+            .find(|s| !s.leased && !skip.contains(&s.account))?;
+        slot.leased = true;
+        Some(*slot)
+    }
+
+    /// 占用指定的空闲账户（关闭、回收期间避免被租出）
+    fn take(&self, account: &Pubkey) -> Result<(), NonceError> {
+        let mut slots = self.slots.lock().unwrap();
+        let slot = slots
+            .iter_mut()
+            .find(|s| s.account == *account)
+            .ok_or(NonceError::NotInPool(*account))?;
+        if slot.leased {
+            return Err(NonceError::Leased(*account));
+        }
+        slot.leased = true;
+        Ok(())
+    }
+
+    /// 直接采用新的 nonce 值并清除 dirty 标记，用于主动推进 nonce 之后
+    fn reset_hash(&self, account: &Pubkey, hash: Hash) {
+        let mut slots = self.slots.lock().unwrap();
+        if let Some(slot) = slots.iter_mut().find(|s| s.account == *account) {
+            slot.hash = hash;
+            slot.dirty = false;
+        }
+    }
+
+    /// 写回链上读到的 nonce 值。上次租约后 nonce 仍未推进时保留 dirty 标记并返回
+    /// `NotAdvanced`：发出的交易可能还在途，此时复用该 nonce 会与其冲突
+    fn update_hash(&self, account: &Pubkey, hash: Hash) -> Result<Hash, NonceError> {
+        let mut slots = self.slots.lock().unwrap();
+        let slot = slots
+            .iter_mut()
+            .find(|s| s.account == *account)
+            .ok_or(NonceError::NotInPool(*account))?;
+        if slot.dirty && slot.hash == hash {
+            return Err(NonceError::NotAdvanced(*account));
+        }
+        slot.hash = hash;
+        slot.dirty = false;
+        Ok(hash)
+    }
+}
+
+/// durable nonce 账户池，所有账户共用同一个 authority
+#[derive(Clone)]
+pub struct NoncePool {
+    inner: Arc<PoolInner>,
+}
+
+impl NoncePool {
+    /// 创建并初始化 `count` 个 nonce 账户，`payer` 支付租金与手续费
+    pub async fn create(
+        rpc: Arc<RpcClient>,
+        payer: &Keypair,
+        authority: Arc<Keypair>,
+        count: usize,
+    ) -> Result<Self, NonceError> {
+        let rent = rpc
+            .get_minimum_balance_for_rent_exemption(State::size())
+            .await?;
+        let mut accounts = Vec::with_capacity(count);
+        for _ in 0..count {
+            let nonce_keypair = Keypair::new();
+            let ixs = create_nonce_account(
+                &payer.pubkey(),
+                &nonce_keypair.pubkey(),
+                &authority.pubkey(),
+                rent,
+            );
+            let blockhash = rpc.get_latest_blockhash().await?;
+            let tx = Transaction::new_signed_with_payer(
+                &ixs,
+                Some(&payer.pubkey()),
+                &[payer, &nonce_keypair],
+                blockhash,
+            );
+            let sig = rpc.send_and_confirm_transaction(&tx).await?;
+            info!(
+                "[NoncePool] created nonce account {} ({})",
+                nonce_keypair.pubkey(),
+                sig
+            );
+            accounts.push(nonce_keypair.pubkey());
+        }
+        Self::load(rpc, authority, &accounts).await
+    }
+
+    /// 加载已有的 nonce 账户，校验 owner、初始化状态与 authority
+    pub async fn load(
+        rpc: Arc<RpcClient>,
+        authority: Arc<Keypair>,
+        accounts: &[Pubkey],
+    ) -> Result<Self, NonceError> {
+        let fetched = rpc.get_multiple_accounts(accounts).await?;
+        let mut slots = Vec::with_capacity(accounts.len());
+        for (address, account) in accounts.iter().zip(fetched) {
+            let account = account.ok_or(NonceError::AccountNotFound(*address))?;
+            let (actual, hash) = parse_nonce_account(address, &account)?;
+            if actual != authority.pubkey() {
+                return Err(NonceError::AuthorityMismatch {
+                    account: *address,
+                    expected: authority.pubkey(),
+                    actual,
+                });
+            }
+            slots.push(NonceSlot {
+                account: *address,
+                hash,
+                leased: false,
+                dirty: false,
+            });
+        }
+        Ok(Self {
+            inner: Arc::new(PoolInner {
+                rpc,
+                authority,
+                slots: Mutex::new(slots),
+                released: Notify::new(),
+            }),
+        })
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.inner.authority.pubkey()
+    }
+
+    pub fn accounts(&self) -> Vec<Pubkey> {
+        let slots = self.inner.slots.lock().unwrap();
+        slots.iter().map(|s| s.account).collect()
+    }
+
+    /// 当前空闲的账户数
+    pub fn available(&self) -> usize {
+        let slots = self.inner.slots.lock().unwrap();
+        slots.iter().filter(|s| !s.leased).count()
+    }
+
+    /// 租用一个空闲账户，没有空闲账户时返回 `None`。
+    /// 跳过 nonce 尚未推进的账户；所有空闲账户都未推进时返回 `NotAdvanced`，
+    /// 确认旧交易不会落地后可用 `reclaim` 回收
+    pub async fn try_lease(&self) -> Result<Option<NonceLease>, NonceError> {
+        let mut not_advanced = Vec::new();
+        loop {
+            let Some(slot) = self.inner.take_free(&not_advanced) else {
+                return match not_advanced.first() {
+                    Some(account) => Err(NonceError::NotAdvanced(*account)),
+                    None => Ok(None),
+                };
+            };
+
+            let hash = if slot.dirty {
+                match self.refresh(&slot.account).await {
+                    Ok(hash) => hash,
+                    Err(NonceError::NotAdvanced(account)) => {
+                        warn!(
+                            "[NoncePool] nonce {} has not advanced since last lease, skipped",
+                            account
+                        );
+                        self.inner.release(&account, true);
+                        not_advanced.push(account);
+                        continue;
+                    }
+                    Err(e) => {
+                        // 刷新失败时归还账户，保留 dirty 标记
+                        self.inner.release(&slot.account, true);
+                        return Err(e);
+                    }
+                }
+            } else {
+                slot.hash
+            };
+
+            return Ok(Some(NonceLease {
+                pool: Arc::clone(&self.inner),
+                account: slot.account,
+                authority: self.inner.authority.pubkey(),
+                hash,
+                consumed: true,
+            }));
+        }
+    }
+
+    /// 租用一个空闲账户，全部被占用时等待归还；空闲账户的 nonce 均未推进时返回 `NotAdvanced`
+    pub async fn lease(&self) -> Result<NonceLease, NonceError> {
+        loop {
+            let released = self.inner.released.notified();
+            if let Some(lease) = self.try_lease().await? {
+                return Ok(lease);
+            }
+            released.await;
+        }
+    }
+
+    /// 从链上刷新某个账户的 nonce 值，上次租约后仍未推进时返回 `NotAdvanced`
+    pub async fn refresh(&self, account: &Pubkey) -> Result<Hash, NonceError> {
+        let (_, hash) = fetch_nonce_account(&self.inner.rpc, account).await?;
+        self.inner.update_hash(account, hash)
+    }
+
+    /// 关闭一个空闲账户：取回全部 lamports 到 `to` 并移出池
+    pub async fn close(
+        &self,
+        account: &Pubkey,
+        payer: &Keypair,
+        to: &Pubkey,
+    ) -> Result<Signature, NonceError> {
+        self.inner.take(account)?;
+        let result = self.withdraw_all(account, payer, to).await;
+        let mut slots = self.inner.slots.lock().unwrap();
+        match &result {
+            Ok(_) => slots.retain(|s| s.account != *account),
+            Err(_) => {
+                if let Some(slot) = slots.iter_mut().find(|s| s.account == *account) {
+                    slot.leased = false;
+                }
+            }
+        }
+        result
+    }
+
+    /// 回收 nonce 一直未推进的空闲账户（租约发出的交易始终未落地）：
+    /// 提交 `AdvanceNonceAccount` 使在途的旧交易失效，写回新的 nonce 值并清除 dirty 标记。
+    /// 手续费由 authority 支付
+    pub async fn reclaim(&self, account: &Pubkey) -> Result<Signature, NonceError> {
+        self.inner.take(account)?;
+        let result = self.advance(account).await;
+        if let Ok((_, hash)) = &result {
+            self.inner.reset_hash(account, *hash);
+        }
+        self.inner.release(account, false);
+        result.map(|(sig, _)| sig)
+    }
+
+    async fn advance(&self, account: &Pubkey) -> Result<(Signature, Hash), NonceError> {
+        let rpc = &self.inner.rpc;
+        let authority = self.inner.authority.as_ref();
+        let ix = advance_nonce_account(account, &authority.pubkey());
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            blockhash,
+        );
+        let sig = rpc.send_and_confirm_transaction(&tx).await?;
+        let (_, hash) = fetch_nonce_account(rpc, account).await?;
+        info!("[NoncePool] reclaimed nonce account {} ({})", account, sig);
+        Ok((sig, hash))
+    }
+
+    /// 关闭池中所有空闲账户，返回每个账户的关闭结果
+    pub async fn close_all(
+        &self,
+        payer: &Keypair,
+        to: &Pubkey,
+    ) -> Vec<(Pubkey, Result<Signature, NonceError>)> {
+        let mut results = Vec::new();
+        for account in self.accounts() {
+            let result = self.close(&account, payer, to).await;
+            results.push((account, result));
+        }
+        results
+    }
+
+    async fn withdraw_all(
+        &self,
+        account: &Pubkey,
+        payer: &Keypair,
+        to: &Pubkey,
+    ) -> Result<Signature, NonceError> {
+        let rpc = &self.inner.rpc;
+        let lamports = rpc.get_account(account).await?.lamports;
+        let ix = withdraw_nonce_account(account, &self.inner.authority.pubkey(), to, lamports);
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[payer, self.inner.authority.as_ref()],
+            blockhash,
+        );
+        let sig = rpc.send_and_confirm_transaction(&tx).await?;
+        info!("[NoncePool] closed nonce account {} ({})", account, sig);
+        Ok(sig)
+    }
+}
+
+/// nonce 账户租约，drop 时归还到池中
+pub struct NonceLease {
+    pool: Arc<PoolInner>,
+    account: Pubkey,
+    authority: Pubkey,
+    hash: Hash,
+    /// 是否可能已推进 nonce（已发送过交易）
+    consumed: bool,
+}
+
+impl NonceLease {
+    pub fn account(&self) -> Pubkey {
+        self.account
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn hash_param(&self) -> HashParam {
+        HashParam::NonceAccount {
+            account: self.account,
+            authority: self.authority,
+            hash: self.hash,
+        }
+    }
+
+    /// 未发送任何交易时归还，下次租用无需刷新 nonce
+    pub fn release_unused(mut self) {
+        self.consumed = false;
+    }
+}
+
+impl Drop for NonceLease {
+    fn drop(&mut self) {
+        self.pool.release(&self.account, self.consumed);
+    }
+}
+
+#[test]
+fn test_parse_nonce_account() {
+    use solana_nonce::state::{Data, DurableNonce};
+
+    let address = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let durable_nonce = DurableNonce::from_blockhash(&Hash::new_from_array([5; 32]));
+    let account = |state: State, owner: Pubkey| Account {
+        lamports: 1_447_680,
+        data: bincode::serialize(&Versions::new(state)).unwrap(),
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let initialized = State::Initialized(Data::new(authority, durable_nonce, 5_000));
+    let (actual, hash) = parse_nonce_account(
+        &address,
+        &account(initialized.clone(), solana_system_interface::program::ID),
+    )
+    .unwrap();
+    assert_eq!(actual, authority);
+    assert_eq!(hash, *durable_nonce.as_hash());
+
+    assert_eq!(
+        parse_nonce_account(
+            &address,
+            &account(State::Uninitialized, solana_system_interface::program::ID)
+        ),
+        Err(NonceError::Uninitialized(address))
+    );
+    assert!(matches!(
+        parse_nonce_account(
+            &address,
+            &account(initialized.clone(), Pubkey::new_unique())
+        ),
+        Err(NonceError::WrongOwner { .. })
+    ));
+
+    let data = account(initialized, solana_system_interface::program::ID);
+    assert!(matches!(
+        HashParam::from_nonce_account_data(&address, &data, &authority),
+        Ok(HashParam::NonceAccount { hash, .. }) if hash == *durable_nonce.as_hash()
+    ));
+    assert!(matches!(
+        HashParam::from_nonce_account_data(&address, &data, &Pubkey::new_unique()),
+        Err(NonceError::AuthorityMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_refresh_before_nonce_advances() {
+    let account = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let hash = Hash::new_from_array([1; 32]);
+    let slot = |account| NonceSlot {
+        account,
+        hash,
+        leased: false,
+        dirty: false,
+    };
+    let pool = NoncePool {
+        inner: Arc::new(PoolInner {
+            rpc: Arc::new(RpcClient::new("http://127.0.0.1:8899".to_string())),
+            authority: Arc::new(Keypair::new()),
+            slots: Mutex::new(vec![slot(account), slot(other)]),
+            released: Notify::new(),
+        }),
+    };
+
+    // 租用后发送过交易（consumed），归还时标记 dirty
+    let leased = pool.inner.take_free(&[]).unwrap();
+    assert_eq!(leased.account, account);
+    pool.inner.release(&account, true);
+
+    // 再次租用前刷新，链上 nonce 尚未推进：保留 dirty，跳过该账户
+    assert!(pool.inner.take_free(&[]).unwrap().dirty);
+    assert_eq!(
+        pool.inner.update_hash(&account, hash),
+        Err(NonceError::NotAdvanced(account))
+    );
+    pool.inner.release(&account, true);
+    assert!(pool.inner.slots.lock().unwrap()[0].dirty);
+    assert_eq!(pool.inner.take_free(&[account]).unwrap().account, other);
+
+    // nonce 推进后刷新成功
+    let advanced = Hash::new_from_array([2; 32]);
+    assert_eq!(pool.inner.update_hash(&account, advanced), Ok(advanced));
+    assert!(!pool.inner.slots.lock().unwrap()[0].dirty);
+}
+
+#[test]
+fn test_reclaim_clears_dirty() {
+    let account = Pubkey::new_unique();
+    let hash = Hash::new_from_array([1; 32]);
+    let pool = NoncePool {
+        inner: Arc::new(PoolInner {
+            rpc: Arc::new(RpcClient::new("http://127.0.0.1:8899".to_string())),
+            authority: Arc::new(Keypair::new()),
+            slots: Mutex::new(vec![NonceSlot {
+                account,
+                hash,
+                leased: false,
+                dirty: false,
+            }]),
+            released: Notify::new(),
+        }),
+    };
+
+    // 租约发出的交易始终未落地：nonce 不推进，账户一直被跳过
+    pool.inner.take_free(&[]).unwrap();
+    assert_eq!(pool.inner.take(&account), Err(NonceError::Leased(account)));
+    pool.inner.release(&account, true);
+    assert_eq!(
+        pool.inner.update_hash(&account, hash),
+        Err(NonceError::NotAdvanced(account))
+    );
+
+    // reclaim 推进 nonce 后写回新值，归还时不再 dirty
+    let advanced = Hash::new_from_array([2; 32]);
+    pool.inner.take(&account).unwrap();
+    assert!(pool.inner.take_free(&[]).is_none());
+    pool.inner.reset_hash(&account, advanced);
+    pool.inner.release(&account, false);
+    let slot = pool.inner.take_free(&[]).unwrap();
+    assert!(!slot.dirty);
+    assert_eq!(slot.hash, advanced);
+    let unknown = Pubkey::new_unique();
+    assert_eq!(
+        pool.inner.take(&unknown),
+        Err(NonceError::NotInPool(unknown))
+    );
+}
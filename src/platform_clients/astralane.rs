@@ -14,20 +14,19 @@ struct AstralaneSendBundleResponse {
     pub _id: Option<u64>,
 }
 
-use base64::Engine;
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
-use solana_sdk::signature::Signature;
-
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SubmitReceipt};
+use crate::tip_accounts;
 
 pub const ASTRALANE_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("astrazznxsGUhWShqgNtAdfrzP2G83DzcWVJDxwV9bF"),
@@ -102,7 +101,7 @@ impl Astralane {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for Astralane {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("astralane send: ", {
             let req_json = json!({
                 "jsonrpc": "2.0",
@@ -120,6 +119,7 @@ impl crate::platform_clients::SendTxEncoded for Astralane {
             // println!("[astralane/send_tx] endpoint: {}", self.endpoint);
             // println!("[astralane/send_tx] api-key(header): {}", self.auth_token);
             // println!("[astralane/send_tx] request body: {}", req_json);
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&self.endpoint)
@@ -129,21 +129,20 @@ impl crate::platform_clients::SendTxEncoded for Astralane {
                 .send()
                 .await;
             // println!("[astralane/send_tx] res: {res:?}");
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => {
-                        // println!("[astralane/send_tx] response text error: {}", e);
-                        return Err(format!("response text error: {}", e));
-                    }
-                },
-                Err(e) => {
-                    // println!("[astralane/send_tx] send error: {}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("[astralane/send_tx] send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             info!("[astralane/send_tx] response: {}", response);
-            Ok(())
+            let result = parse_json_rpc_result(&response)?;
+            Ok(SubmitReceipt {
+                platform: PlatformName::Astralane,
+                endpoint: self.endpoint.clone(),
+                latency,
+                http_status: Some(status),
+                server_id: result.as_str().map(str::to_string),
+                raw: Some(response),
+            })
         })
     }
 }
@@ -153,20 +152,14 @@ impl crate::platform_clients::SendBundle for Astralane {
     async fn send_bundle(
         &self,
         txs: &[crate::platform_clients::SolTx],
-    ) -> Result<Vec<Signature>, String> {
+    ) -> Result<SubmitReceipt, SendError> {
         // 将所有交易序列化并 base64 编码
         let mut encoded_txs = Vec::with_capacity(txs.len());
-        let mut sigs: Vec<Signature> = Vec::with_capacity(txs.len());
         for tx in txs {
-            let encode_tx = match bincode::serialize(tx) {
-                Ok(bytes) => base64::prelude::BASE64_STANDARD.encode(&bytes),
-                Err(e) => return Err(format!("bincode serialize error: {}", e)),
-            };
-            encoded_txs.push(encode_tx);
-            sigs.push(tx.sig());
+            encoded_txs.push(tx.to_base64()?);
         }
 
-        let request_body = match serde_json::to_string(&json!({
+        let request_body = serde_json::to_string(&json!({
             "id": 1,
             "jsonrpc": "2.0",
             "method": "sendBundle",
@@ -178,11 +171,10 @@ impl crate::platform_clients::SendBundle for Astralane {
                     "revertProtection": false
                 }
             ]
-        })) {
-            Ok(body) => body,
-            Err(e) => return Err(format!("serde_json error: {}", e)),
-        };
+        }))
+        .map_err(|e| SendError::Serialize(format!("serde_json error: {}", e)))?;
 
+        let started = Instant::now();
         let res = self
             .http_client
             .post(&self.endpoint)
@@ -192,19 +184,10 @@ impl crate::platform_clients::SendBundle for Astralane {
             .send()
             .await;
 
-        let response = match res {
-            Ok(resp) => match resp.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    // println!("[astralane/send_bundle] response text error: {}", e);
-                    return Err(format!("response text error: {}", e));
-                }
-            },
-            Err(e) => {
-                log::error!("[astralane/send_bundle] send error: {:?}", e);
-                return Err(format!("send error: {}", e));
-            }
-        };
+        let (status, response) = read_http_response(res).await.inspect_err(|e| {
+            log::error!("[astralane/send_bundle] send error: {}", e);
+        })?;
+        let latency = started.elapsed();
 
         log::info!("astralane raw response: {:?}", response);
 
@@ -213,17 +196,27 @@ impl crate::platform_clients::SendBundle for Astralane {
             Ok(resp_obj) => {
                 if let Some(result) = resp_obj.result {
                     log::info!("astralane bundle signatures: {:?}", result);
-                    Ok(sigs)
+                    Ok(SubmitReceipt {
+                        platform: PlatformName::Astralane,
+                        endpoint: self.endpoint.clone(),
+                        latency,
+                        http_status: Some(status),
+                        server_id: Some(result.join(",")),
+                        raw: Some(response),
+                    })
                 } else if let Some(err) = resp_obj.error {
-                    Err(format!("astralane error: {}", err))
+                    Err(SendError::from_rpc_error(&err))
                 } else {
-                    Err(format!("astralane unknown response: {}", response))
+                    Err(SendError::Parse(format!(
+                        "astralane unknown response: {}",
+                        response
+                    )))
                 }
             }
-            Err(e) => Err(format!(
+            Err(e) => Err(SendError::Parse(format!(
                 "astralane response parse error: {}, raw: {}",
                 e, response
-            )),
+            ))),
         }
     }
 }
@@ -233,19 +226,19 @@ impl crate::platform_clients::BuildTx for Astralane {
         PlatformName::Astralane
     }
     fn get_tip_address(&self) -> Pubkey {
-        *ASTRALANE_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| ASTRALANE_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Astralane, ASTRALANE_TIP_ACCOUNTS)
     }
 
     fn get_min_tip_amount(&self) -> u64 {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        ASTRALANE_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Astralane, ASTRALANE_TIP_ACCOUNTS)
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
 
@@ -2,18 +2,20 @@ use anyhow::Result;
 use astralane_quic_client::AstralaneQuicClient;
 use base64::Engine;
 use log::info;
-use rand::seq::IndexedRandom;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 use solana_sdk::transaction::Transaction;
 use std::env;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Instant;
 
 use crate::constants::REGION;
+use crate::error::SendError;
 use crate::platform_clients::astralane::ASTRALANE_TIP_ACCOUNTS;
 use crate::platform_clients::astralane_quic::get_quic_endpoint;
-use crate::platform_clients::{BuildTx, PlatformName, Region, SendTxEncoded};
+use crate::platform_clients::{BuildTx, PlatformName, Region, SendTxEncoded, SubmitReceipt};
+use crate::tip_accounts;
 
 #[derive(Clone)]
 pub struct AstralaneQuic {
@@ -62,14 +64,14 @@ impl AstralaneQuic {
     }
 
     // Sync version for convenience
-    pub async fn send_transaction(&self, tx: &Transaction) -> Result<Signature, String> {
+    pub async fn send_transaction(&self, tx: &Transaction) -> Result<Signature, SendError> {
         let tx_bytes = bincode::serialize(tx)
-            .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+            .map_err(|e| SendError::Serialize(format!("Failed to serialize transaction: {}", e)))?;
 
         self.client
             .send_transaction(&tx_bytes)
             .await
-            .map_err(|e| format!("Failed to send QUIC transaction: {}", e))?;
+            .map_err(|e| SendError::Transport(format!("Failed to send QUIC transaction: {}", e)))?;
 
         let sig = tx.signatures[0];
         Ok(sig)
@@ -84,33 +86,35 @@ impl fmt::Display for AstralaneQuic {
 
 #[async_trait::async_trait]
 impl SendTxEncoded for AstralaneQuic {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         // Decode base64 to bytes
         let tx_bytes = base64::prelude::BASE64_STANDARD
             .decode(tx_base64)
-            .map_err(|e| format!("Failed to decode base64: {}", e))?;
+            .map_err(|e| SendError::Serialize(format!("Failed to decode base64: {}", e)))?;
+        self.send_tx_raw(&tx_bytes).await
+    }
 
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
         // Send via QUIC
+        let started = Instant::now();
         self.client
-            .send_transaction(&tx_bytes)
+            .send_transaction(tx_bytes)
             .await
-            .map_err(|e| format!("Astralane QUIC send error: {}", e))?;
-
-        // Try parsing as VersionedTransaction first (V0), fallback to Transaction (legacy)
-        let sig = if let Ok(v0tx) =
-            bincode::deserialize::<solana_sdk::transaction::VersionedTransaction>(&tx_bytes)
-        {
-            v0tx.signatures[0]
-        } else if let Ok(tx) =
-            bincode::deserialize::<solana_sdk::transaction::Transaction>(&tx_bytes)
-        {
-            tx.signatures[0]
-        } else {
-            return Err("Failed to deserialize transaction for signature".to_string());
-        };
-        info!("[AstralaneQuic] Sent transaction signature: {}", sig);
-
-        Ok(())
+            .map_err(|e| SendError::Transport(format!("Astralane QUIC send error: {}", e)))?;
+        let latency = started.elapsed();
+        info!(
+            "[AstralaneQuic] Sent transaction ({} bytes)",
+            tx_bytes.len()
+        );
+
+        Ok(SubmitReceipt {
+            platform: PlatformName::Astralane,
+            endpoint: self.endpoint.clone(),
+            latency,
+            http_status: None,
+            server_id: None,
+            raw: None,
+        })
     }
 }
 
@@ -121,10 +125,7 @@ impl crate::platform_clients::BuildTx for AstralaneQuic {
 
     fn get_tip_address(&self) -> Pubkey {
         // 随机选择一个 tip 账户
-        *ASTRALANE_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| ASTRALANE_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Astralane, ASTRALANE_TIP_ACCOUNTS)
     }
 
     fn get_min_tip_amount(&self) -> u64 {
@@ -132,6 +133,9 @@ impl crate::platform_clients::BuildTx for AstralaneQuic {
     }
 
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        ASTRALANE_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Astralane, ASTRALANE_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
     }
 }
@@ -0,0 +1,413 @@
+//! Block Engine 风格 gRPC 的公共部分（Harmonic / Jito 共用）
+//!
+//! 两家的 auth / searcher proto 结构一致，区别只在 challenge 时的 `Role`：
+//! Harmonic 为 `Role::Searcher`（3），Jito 为 `Role::JitoSearcher`（1）。
+
+use crate::constants::{MAX_BUNDLE_TXS, PACKET_DATA_SIZE};
+use crate::error::SendError;
+use crate::platform_clients::harmonic_proto::{
+    auth::{
+        GenerateAuthChallengeRequest, GenerateAuthTokensRequest, RefreshAccessTokenRequest, Role,
+        Token, auth_service_client::AuthServiceClient,
+    },
+    bundle::Bundle,
+    packet::{Meta, Packet, PacketFlags},
+    searcher::searcher_service_client::SearcherServiceClient,
+    shared::Header,
+};
+
+use anyhow::{Context, anyhow};
+use log::{info, warn};
+use prost_types::Timestamp;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, OnceCell};
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, ClientTlsConfig};
+use tonic::{Code, Request};
+
+/// access token 剩余有效期低于该值时提前刷新
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// 带过期时间的 token；服务端未给出过期时间时视为一直有效，直到收到 `Unauthenticated`
+#[derive(Clone)]
+struct CachedToken {
+    value: String,
+    expires_at: Option<SystemTime>,
+}
+
+impl CachedToken {
+    fn from_proto(token: Token) -> Self {
+        let expires_at = token.expires_at_utc.and_then(|ts| {
+            let secs = u64::try_from(ts.seconds).ok()?;
+            Some(UNIX_EPOCH + Duration::new(secs, ts.nanos.clamp(0, 999_999_999) as u32))
+        });
+        Self {
+            value: token.value,
+            expires_at,
+        }
+    }
+
+    /// 在 `now + margin` 时仍然有效
+    fn fresh_at(&self, now: SystemTime, margin: Duration) -> bool {
+        self.expires_at.is_none_or(|exp| now + margin < exp)
+    }
+}
+
+/// 标记认证 RPC（challenge / token / refresh）本身的失败，`classify_error` 据此映射为 `Auth`
+#[derive(Debug)]
+struct AuthFailed;
+
+impl std::fmt::Display for AuthFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "block engine auth failed")
+    }
+}
+
+#[derive(Default)]
+struct Tokens {
+    access: Option<CachedToken>,
+    refresh: Option<CachedToken>,
+}
+
+/// 单个 block engine endpoint 的长连接会话。
+///
+/// - `Channel` 首次使用时建立，之后复用（tonic 内部负责断线重连）
+/// - access token 缓存到临近过期，优先用 `RefreshAccessToken` 续期，失败再走完整 challenge
+/// - 调用返回 `Unauthenticated` 时丢弃 token 重新认证并重试一次
+pub(crate) struct BlockEngineSession {
+    endpoint: String,
+    searcher: Arc<Keypair>,
+    role: Role,
+    channel: OnceCell<Channel>,
+    tokens: Mutex<Tokens>,
+}
+
+impl BlockEngineSession {
+    pub(crate) fn new(endpoint: impl Into<String>, searcher: Arc<Keypair>, role: Role) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            searcher,
+            role,
+            channel: OnceCell::new(),
+            tokens: Mutex::new(Tokens::default()),
+        }
+    }
+
+    pub(crate) fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    async fn channel(&self) -> anyhow::Result<Channel> {
+        self.channel
+            .get_or_try_init(|| connect(&self.endpoint))
+            .await
+            .cloned()
+    }
+
+    /// 返回可用的 access token，必要时续期或重新认证。
+    pub(crate) async fn access_token(&self) -> anyhow::Result<String> {
+        let mut tokens = self.tokens.lock().await;
+        let now = SystemTime::now();
+        if let Some(access) = tokens
+            .access
+            .as_ref()
+            .filter(|t| t.fresh_at(now, TOKEN_REFRESH_MARGIN))
+        {
+            return Ok(access.value.clone());
+        }
+
+        let channel = self.channel().await?;
+        if let Some(refresh) = tokens
+            .refresh
+            .clone()
+            .filter(|t| t.fresh_at(now, TOKEN_REFRESH_MARGIN))
+        {
+            match refresh_access_token(channel.clone(), refresh.value).await {
+                Ok(access) => {
+                    info!("[BlockEngine] {} access token refreshed", self.endpoint);
+                    tokens.access = Some(access.clone());
+                    return Ok(access.value);
+                }
+                Err(e) => warn!("[BlockEngine] {} refresh failed: {:#}", self.endpoint, e),
+            }
+        }
+
+        let (access, refresh) = authenticate_on(channel, &self.searcher, self.role)
+            .await
+            .context(AuthFailed)?;
+        info!("[BlockEngine] {} authenticated", self.endpoint);
+        tokens.access = Some(access.clone());
+        tokens.refresh = refresh;
+        Ok(access.value)
+    }
+
+    /// 丢弃缓存的 token，下一次调用重新走 challenge-response。
+    pub(crate) async fn invalidate(&self) {
+        *self.tokens.lock().await = Tokens::default();
+    }
+
+    /// 以已认证的 searcher client 执行一次调用；`Unauthenticated` 时重新认证并重试一次。
+    pub(crate) async fn call<T, F, Fut>(&self, mut f: F) -> anyhow::Result<T>
+    where
+        F: FnMut(SearcherServiceClient<Channel>, String) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let client = SearcherServiceClient::new(self.channel().await?);
+        let token = self.access_token().await?;
+        match f(client.clone(), token).await {
+            Err(e) if is_unauthenticated(&e) => {
+                warn!("[BlockEngine] {} unauthenticated, re-auth", self.endpoint);
+                self.invalidate().await;
+                let token = self.access_token().await?;
+                f(client, token).await
+            }
+            other => other,
+        }
+    }
+}
+
+fn is_unauthenticated(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<tonic::Status>()
+        .is_some_and(|status| status.code() == Code::Unauthenticated)
+}
+
+/// 在已有 channel 上完成 challenge-response，返回 (access token, refresh token)。
+async fn authenticate_on(
+    channel: Channel,
+    searcher: &Keypair,
+    role: Role,
+) -> anyhow::Result<(CachedToken, Option<CachedToken>)> {
+    let mut client = AuthServiceClient::new(channel);
+    let pubkey = searcher.pubkey();
+
+    // 1. 请求 challenge
+    let challenge_resp = client
+        .generate_auth_challenge(GenerateAuthChallengeRequest {
+            role: role as i32,
+            pubkey: pubkey.to_bytes().to_vec(),
+        })
+        .await
+        .context("GenerateAuthChallenge failed")?
+        .into_inner();
+
+    // 2. 签名 "{pubkey}-{challenge}"
+    let challenge = format!("{}-{}", pubkey, challenge_resp.challenge);
+    let signed = searcher.sign_message(challenge.as_bytes());
+
+    // 3. 换取 access / refresh token
+    let tokens = client
+        .generate_auth_tokens(GenerateAuthTokensRequest {
+            challenge,
+            client_pubkey: pubkey.to_bytes().to_vec(),
+            signed_challenge: signed.as_ref().to_vec(),
+        })
+        .await
+        .context("GenerateAuthTokens failed")?
+        .into_inner();
+
+    let access = tokens
+        .access_token
+        .ok_or_else(|| anyhow!("auth response missing access_token"))?;
+    Ok((
+        CachedToken::from_proto(access),
+        tokens.refresh_token.map(CachedToken::from_proto),
+    ))
+}
+
+/// 用 refresh token 换新的 access token。
+async fn refresh_access_token(
+    channel: Channel,
+    refresh_token: String,
+) -> anyhow::Result<CachedToken> {
+    let resp = AuthServiceClient::new(channel)
+        .refresh_access_token(RefreshAccessTokenRequest { refresh_token })
+        .await
+        .context("RefreshAccessToken failed")?
+        .into_inner();
+    resp.access_token
+        .map(CachedToken::from_proto)
+        .ok_or_else(|| anyhow!("refresh response missing access_token"))
+}
+
+pub(crate) async fn connect(endpoint: &str) -> anyhow::Result<Channel> {
+    let ep = Channel::from_shared(endpoint.to_string())
+        .with_context(|| format!("invalid endpoint: {endpoint}"))?
+        .tls_config(ClientTlsConfig::new().with_enabled_roots())?;
+    ep.connect().await.context("connect gRPC endpoint failed")
+}
+
+/// 附加 `authorization: Bearer {token}` 元数据。
+pub(crate) fn with_bearer<T>(msg: T, token: &str) -> anyhow::Result<Request<T>> {
+    let mut req = Request::new(msg);
+    let bearer = MetadataValue::try_from(format!("Bearer {token}"))
+        .context("build bearer metadata failed")?;
+    req.metadata_mut().insert("authorization", bearer);
+    Ok(req)
+}
+
+/// 发送前校验 bundle：交易数在 `1..=MAX_BUNDLE_TXS`，每笔不超过 `PACKET_DATA_SIZE`。
+pub(crate) fn check_bundle(txs: &[Vec<u8>]) -> Result<(), SendError> {
+    if txs.is_empty() || txs.len() > MAX_BUNDLE_TXS {
+        return Err(SendError::BundleSize {
+            count: txs.len(),
+            min: 1,
+            max: MAX_BUNDLE_TXS,
+        });
+    }
+    if let Some(tx) = txs.iter().find(|tx| tx.len() > PACKET_DATA_SIZE) {
+        return Err(SendError::Oversize {
+            size: tx.len(),
+            max: PACKET_DATA_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// 按顺序把序列化后的交易装入 bundle，每个 packet 的 `Meta.size` 为对应交易字节数。
+pub(crate) fn bundle_of(txs: Vec<Vec<u8>>) -> anyhow::Result<Bundle> {
+    Ok(Bundle {
+        header: Some(Header {
+            ts: Some(now_ts()?),
+        }),
+        packets: txs
+            .into_iter()
+            .map(|data| Packet {
+                meta: Some(Meta {
+                    size: data.len() as u64,
+                    addr: String::new(),
+                    port: 0,
+                    flags: Some(PacketFlags {
+                        discard: false,
+                        forwarded: false,
+                        repair: false,
+                        simple_vote_tx: false,
+                        tracer_packet: false,
+                        from_staked_node: false,
+                    }),
+                    sender_stake: 0,
+                }),
+                data,
+            })
+            .collect(),
+    })
+}
+
+pub(crate) fn now_ts() -> anyhow::Result<Timestamp> {
+    let d = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("system clock error")?;
+    Ok(Timestamp {
+        seconds: d.as_secs() as i64,
+        nanos: d.subsec_nanos() as i32,
+    })
+}
+
+/// 将内部 anyhow 错误映射为 `SendError`：优先按 gRPC status / transport 错误归类
+/// （认证过程中的网络抖动仍可重试），其余认证失败（如 token 缺失）为 `Auth`，
+/// 元数据编码错误为 `Serialize`，其余（endpoint 配置等）为 `Transport`。
+pub(crate) fn classify_error(e: &anyhow::Error) -> SendError {
+    if let Some(status) = e.downcast_ref::<tonic::Status>() {
+        match SendError::from(status.clone()) {
+            SendError::Rejected { code, .. } => SendError::Rejected {
+                code,
+                message: format!("{:#}", e),
+            },
+            other => other,
+        }
+    } else if e.downcast_ref::<tonic::transport::Error>().is_some() {
+        SendError::Transport(format!("{:#}", e))
+    } else if e.downcast_ref::<AuthFailed>().is_some() {
+        SendError::Auth(format!("{:#}", e))
+    } else if e
+        .downcast_ref::<tonic::metadata::errors::InvalidMetadataValue>()
+        .is_some()
+    {
+        SendError::Serialize(format!("{:#}", e))
+    } else {
+        SendError::Transport(format!("{:#}", e))
+    }
+}
+
+#[test]
+fn test_block_engine_bundle() {
+    let bundle = bundle_of(vec![vec![1; 3], vec![2; 5]]).unwrap();
+    let sizes: Vec<_> = bundle
+        .packets
+        .iter()
+        .map(|p| (p.data[0], p.meta.as_ref().unwrap().size))
+        .collect();
+    assert_eq!(sizes, vec![(1, 3), (2, 5)]);
+
+    assert!(check_bundle(&vec![vec![0; PACKET_DATA_SIZE]; MAX_BUNDLE_TXS]).is_ok());
+    assert_eq!(
+        check_bundle(&[]),
+        Err(SendError::BundleSize {
+            count: 0,
+            min: 1,
+            max: 5
+        })
+    );
+    assert_eq!(
+        check_bundle(&vec![vec![0; 10]; MAX_BUNDLE_TXS + 1]),
+        Err(SendError::BundleSize {
+            count: 6,
+            min: 1,
+            max: 5
+        })
+    );
+    assert_eq!(
+        check_bundle(&[vec![0; 10], vec![0; PACKET_DATA_SIZE + 1]]),
+        Err(SendError::Oversize {
+            size: PACKET_DATA_SIZE + 1,
+            max: PACKET_DATA_SIZE
+        })
+    );
+
+    let err = anyhow::Error::new(tonic::Status::unauthenticated("expired")).context("SendBundle");
+    assert!(matches!(classify_error(&err), SendError::Auth(_)));
+    let err = anyhow!("auth response missing access_token").context(AuthFailed);
+    assert!(matches!(classify_error(&err), SendError::Auth(_)));
+    // 认证 RPC 遇到网络抖动仍可重试
+    let err = anyhow::Error::new(tonic::Status::unavailable("connection reset"))
+        .context("GenerateAuthChallenge failed")
+        .context(AuthFailed);
+    assert!(classify_error(&err).is_retryable());
+    let err = with_bearer((), "bad\ntoken").unwrap_err();
+    assert!(matches!(classify_error(&err), SendError::Serialize(_)));
+    let err = anyhow!("invalid uri").context("invalid endpoint: bad");
+    assert!(
+        matches!(classify_error(&err), SendError::Transport(msg) if msg.contains("invalid endpoint"))
+    );
+}
+
+#[test]
+fn test_block_engine_token_expiry() {
+    let now = SystemTime::now();
+    let ts = |t: SystemTime| {
+        let d = t.duration_since(UNIX_EPOCH).unwrap();
+        Timestamp {
+            seconds: d.as_secs() as i64,
+            nanos: d.subsec_nanos() as i32,
+        }
+    };
+    let token = |exp: Option<SystemTime>| {
+        CachedToken::from_proto(Token {
+            value: "t".to_string(),
+            expires_at_utc: exp.map(ts),
+        })
+    };
+
+    assert!(token(Some(now + Duration::from_secs(600))).fresh_at(now, TOKEN_REFRESH_MARGIN));
+    // 剩余有效期不足 margin 时需要提前刷新
+    assert!(!token(Some(now + Duration::from_secs(30))).fresh_at(now, TOKEN_REFRESH_MARGIN));
+    assert!(!token(Some(now - Duration::from_secs(1))).fresh_at(now, Duration::ZERO));
+    assert!(token(None).fresh_at(now, TOKEN_REFRESH_MARGIN));
+
+    let status =
+        anyhow::Error::new(tonic::Status::unauthenticated("expired")).context("SendBundle");
+    assert!(is_unauthenticated(&status));
+    assert!(!is_unauthenticated(&anyhow!("other")));
+}
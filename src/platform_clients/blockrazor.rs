@@ -5,16 +5,18 @@ impl fmt::Display for Blockrazor {
     }
 }
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{BuildTx, PlatformName, Region, SendTxEncoded};
+use crate::error::{SendError, read_http_response};
+use crate::platform_clients::{BuildTx, PlatformName, Region, SendTxEncoded, SubmitReceipt};
+use crate::tip_accounts;
 
 const BLOCKRAZOR_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("FjmZZrFvhnqqb9ThCuMVnENaM3JGVuGWNyCAxRJcFpg9"),
@@ -101,10 +103,7 @@ impl Blockrazor {
     }
 
     pub fn get_tip_address(&self) -> Pubkey {
-        *BLOCKRAZOR_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| BLOCKRAZOR_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Blockrazor, BLOCKRAZOR_TIP_ACCOUNTS)
     }
 }
 
@@ -120,8 +119,9 @@ fn read_auth_token_from_env() -> String {
 
 #[async_trait::async_trait]
 impl SendTxEncoded for Blockrazor {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("blockrazor send:", {
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&self.endpoint)
@@ -133,18 +133,23 @@ impl SendTxEncoded for Blockrazor {
                 }))
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("blockrazor send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             log::info!("{:?}", response);
-            Ok(())
+            // 成功响应形如 {"signature": "..."}，解析失败不影响提交结果
+            let signature = serde_json::from_str::<serde_json::Value>(&response)
+                .ok()
+                .and_then(|v| v.get("signature")?.as_str().map(str::to_string));
+            Ok(SubmitReceipt {
+                platform: PlatformName::Blockrazor,
+                endpoint: self.endpoint.clone(),
+                latency,
+                http_status: Some(status),
+                server_id: signature,
+                raw: Some(response),
+            })
         })
     }
 }
@@ -160,8 +165,11 @@ impl BuildTx for Blockrazor {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        BLOCKRAZOR_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Blockrazor, BLOCKRAZOR_TIP_ACCOUNTS)
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
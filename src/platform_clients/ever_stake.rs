@@ -1,15 +1,17 @@
 use base64::Engine;
 use log::info;
-use rand::seq::IndexedRandom;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::REGION;
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::SendError;
+use crate::platform_clients::{PlatformName, Region, SubmitReceipt};
+use crate::tip_accounts;
 
 pub const EVER_STAKE_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("J4cL8c22KNLHwheuWxK1SCYBWASWPGhEi6xvcGyf6o3S"),
@@ -71,18 +73,29 @@ impl EverStake {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for EverStake {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("ever stake rpc send: ", {
             let bytes = base64::prelude::BASE64_STANDARD
                 .decode(tx_base64)
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| SendError::Serialize(format!("base64 decode failed: {}", e)))?;
 
-            let tx: solana_sdk::transaction::Transaction =
-                bincode::deserialize(&bytes).map_err(|e| e.to_string())?;
+            let tx: solana_sdk::transaction::Transaction = bincode::deserialize(&bytes)
+                .map_err(|e| SendError::Serialize(format!("deserialize tx failed: {}", e)))?;
 
+            let started = Instant::now();
             match self.json_rpc_client.send_transaction(&tx).await {
-                Ok(_) => Ok(()),
-                Err(e) => Err(format!("Everstake send error: {}", e)),
+                Ok(sig) => Ok(SubmitReceipt {
+                    platform: PlatformName::EverStake,
+                    endpoint: self.json_rpc_client.url(),
+                    latency: started.elapsed(),
+                    http_status: None,
+                    server_id: Some(sig.to_string()),
+                    raw: None,
+                }),
+                Err(e) => {
+                    log::error!("Everstake send error: {}", e);
+                    Err(SendError::from(e))
+                }
             }
         })
     }
@@ -93,17 +106,17 @@ impl crate::platform_clients::BuildTx for EverStake {
         PlatformName::EverStake
     }
     fn get_tip_address(&self) -> Pubkey {
-        *EVER_STAKE_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| EVER_STAKE_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::EverStake, EVER_STAKE_TIP_ACCOUNTS)
     }
 
     fn get_min_tip_amount(&self) -> u64 {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        EVER_STAKE_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::EverStake, EVER_STAKE_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
     }
 }
 
@@ -1,92 +1,281 @@
 use base64::Engine;
 use log::{debug, info, warn};
 use quinn::crypto::rustls::QuicClientConfig;
-use quinn::{Connection, Endpoint};
-use rand::seq::IndexedRandom;
+use quinn::{Connection, Endpoint, IdleTimeout};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{signature::Keypair, transaction::Transaction};
 use solana_tls_utils::{SkipServerVerification, new_dummy_x509_certificate};
-use std::sync::Arc;
-use std::time::Duration;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
 use std::{env, fmt};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 use utils::log_time;
 
 use crate::constants::REGION;
+use crate::error::SendError;
 use crate::platform_clients::ever_stake::EVER_STAKE_TIP_ACCOUNTS;
-use crate::platform_clients::{BuildTx, BuildV0Tx, PlatformName, Region, SendTxEncoded, TxSend};
+use crate::platform_clients::{
+    BuildTx, BuildV0Tx, PlatformName, Region, SendTxEncoded, SubmitReceipt, TxSend,
+};
+use crate::tip_accounts;
 
 const ALPN_SWQOS_TX_PROTOCOL: &[&[u8]] = &[b"solana-tpu"];
+const SERVER_NAME: &str = "everstake_swqos";
+
+/// `EverStakeQuic` 连接参数
+#[derive(Debug, Clone)]
+pub struct EverStakeQuicConfig {
+    /// 保活 PING 间隔，`None` 不发送保活包
+    pub keep_alive_interval: Option<Duration>,
+    /// 空闲超时，超过该时间没有任何活动（含保活包）则断开；`None` 使用 quinn 默认值
+    pub max_idle_timeout: Option<Duration>,
+    /// 断线重连的初始退避间隔，每次失败翻倍
+    pub reconnect_initial_backoff: Duration,
+    /// 断线重连的最大退避间隔
+    pub reconnect_max_backoff: Duration,
+}
+
+impl Default for EverStakeQuicConfig {
+    fn default() -> Self {
+        Self {
+            keep_alive_interval: Some(Duration::from_secs(10)),
+            max_idle_timeout: None,
+            reconnect_initial_backoff: Duration::from_millis(200),
+            reconnect_max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+/// QUIC 连接状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    /// 连接已断开，后台正在按退避重连；`attempt` 为已失败的重连次数
+    Reconnecting {
+        attempt: u32,
+        reason: String,
+    },
+}
 
 #[derive(Clone)]
 pub struct EverStakeQuic {
-    _endpoint: Arc<Endpoint>,
-    connection: Connection,
+    inner: Arc<Inner>,
 }
 
-//Establish a connection to Everstake SWQoS Quic Endpoint
-impl EverStakeQuic {
-    pub const MIN_TIP_AMOUNT_TX: u64 = 0_000_500_000; // 单笔交易最低 tip
-    pub const DEFAULT_TPS: u64 = 1;
-    pub fn get_endpoint() -> String {
-        match *REGION {
-            Region::Frankfurt => "64.130.57.62:11809".to_string(),
-            Region::NewYork => "64.130.59.154:11809".to_string(),
-            Region::Amsterdam => "74.118.140.197:11809".to_string(),
-            Region::Tokyo => "208.91.107.171:11809".to_string(),
-            _ => "64.130.57.62:11809".to_string(),
+struct Inner {
+    endpoint: Endpoint,
+    client_config: quinn::ClientConfig,
+    remote: SocketAddr,
+    config: EverStakeQuicConfig,
+    connection: RwLock<Connection>,
+    /// 同一时间只允许一个重连
+    reconnect_lock: tokio::sync::Mutex<()>,
+    state: watch::Sender<ConnectionState>,
+    supervisor: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if let Some(task) = self.supervisor.lock().unwrap().take() {
+            task.abort();
         }
     }
+}
 
-    pub async fn new() -> Result<Self, String> {
-        let keypair_base58_string = std::env::var("EVER_STAKE_QUIC_KEYPAIR").unwrap_or_default();
-        let keypair = Keypair::from_base58_string(&keypair_base58_string);
-        let (cert, key) = new_dummy_x509_certificate(&keypair);
+impl Inner {
+    fn connection(&self) -> Connection {
+        self.connection.read().unwrap().clone()
+    }
 
-        let mut crypto = rustls::ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(SkipServerVerification::new())
-            .with_client_auth_cert(vec![cert], key)
-            .map_err(|err| err.to_string())?;
+    /// 尝试一次重连。`stale` 为调用方发现已断开的连接，若已被其他调用方换掉则直接返回新连接。
+    async fn reconnect(&self, stale: &Connection) -> Result<Connection, SendError> {
+        let _guard = self.reconnect_lock.lock().await;
+        let current = self.connection();
+        if current.stable_id() != stale.stable_id() && current.close_reason().is_none() {
+            // 其他调用方已换上新连接，状态可能仍停在 Reconnecting
+            self.state.send_replace(ConnectionState::Connected);
+            return Ok(current);
+        }
+        let connection = connect(&self.endpoint, &self.client_config, self.remote)
+            .await
+            .map_err(SendError::Transport)?;
+        info!("[EverStakeQuic] reconnected to {}", self.remote);
+        *self.connection.write().unwrap() = connection.clone();
+        self.state.send_replace(ConnectionState::Connected);
+        Ok(connection)
+    }
+}
 
-        crypto.alpn_protocols = ALPN_SWQOS_TX_PROTOCOL.iter().map(|p| p.to_vec()).collect();
+/// 指数退避：从初始间隔开始每次翻倍，不超过上限
+struct Backoff {
+    next: Duration,
+    max: Duration,
+}
 
-        let client_crypto = QuicClientConfig::try_from(crypto)
-            .map_err(|err| "failed to convert rustls config into quinn crypto config")?;
-        let mut client_config = quinn::ClientConfig::new(Arc::new(client_crypto));
-        let mut transport_config = quinn::TransportConfig::default();
+impl Backoff {
+    fn new(config: &EverStakeQuicConfig) -> Self {
+        Self {
+            next: config.reconnect_initial_backoff,
+            max: config.reconnect_max_backoff,
+        }
+    }
 
-        // 设置保活间隔。例如每 10 秒发送一个 PING 帧
-        // 如果不设置，默认为 None（不发送保活包）
-        transport_config.keep_alive_interval(Some(Duration::from_secs(10)));
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+}
 
-        // (可选) 设置空闲超时时间，如果 30 秒内没有任何活动且没有保活包，则断开连接
-        // transport_config.max_idle_timeout(Some(Duration::from_secs(30).try_into().unwrap()));
+/// 监督连接：断开后按指数退避重连，直到成功或客户端被释放
+async fn supervise(inner: Weak<Inner>) {
+    loop {
+        let Some(connection) = inner.upgrade().map(|inner| inner.connection()) else {
+            return;
+        };
+        let reason = connection.closed().await;
+        let Some(config) = inner.upgrade().map(|strong| {
+            warn!(
+                "[EverStakeQuic] connection to {} closed: {}",
+                strong.remote, reason
+            );
+            strong.config.clone()
+        }) else {
+            return;
+        };
 
-        client_config.transport_config(Arc::new(transport_config)); // 将配置注入
+        let reconnected = reconnect_with_backoff(
+            &config,
+            reason.to_string(),
+            |state| {
+                inner
+                    .upgrade()
+                    .map(|strong| strong.state.send_replace(state))
+                    .is_some()
+            },
+            || {
+                let inner = inner.clone();
+                let connection = connection.clone();
+                async move {
+                    let strong = inner.upgrade()?;
+                    Some(strong.reconnect(&connection).await.map(|_| ()))
+                }
+            },
+        )
+        .await;
+        if !reconnected {
+            return;
+        }
+    }
+}
 
-        let mut endpoint = Endpoint::client("0.0.0.0:0".parse().map_err(|_| "fail to parse ip")?)
-            .map_err(|e| e.to_string())?;
-        endpoint.set_default_client_config(client_config.clone());
+/// 重连循环：先发布 `Reconnecting { attempt: 0 }`，之后每次失败发布新的失败次数并按退避等待，
+/// 成功（含复用其他调用方已换上的连接）后发布 `Connected`。
+/// `publish` / `reconnect` 返回 `false` / `None` 表示客户端已释放，此时返回 `false`。
+async fn reconnect_with_backoff<P, R, Fut>(
+    config: &EverStakeQuicConfig,
+    reason: String,
+    mut publish: P,
+    mut reconnect: R,
+) -> bool
+where
+    P: FnMut(ConnectionState) -> bool,
+    R: FnMut() -> Fut,
+    Fut: Future<Output = Option<Result<(), SendError>>>,
+{
+    let mut backoff = Backoff::new(config);
+    let mut attempt = 0;
+    if !publish(ConnectionState::Reconnecting { attempt, reason }) {
+        return false;
+    }
+    loop {
+        match reconnect().await {
+            None => return false,
+            Some(Ok(())) => return publish(ConnectionState::Connected),
+            Some(Err(e)) => {
+                attempt += 1;
+                let delay = backoff.next_delay();
+                warn!(
+                    "[EverStakeQuic] reconnect attempt {} failed: {}, retry in {:?}",
+                    attempt, e, delay
+                );
+                if !publish(ConnectionState::Reconnecting {
+                    attempt,
+                    reason: e.to_string(),
+                }) {
+                    return false;
+                }
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
 
-        let connection = endpoint
-            .connect_with(
-                client_config,
-                Self::get_endpoint()
-                    .parse()
-                    .map_err(|_| "fail to get endpoint")?,
-                "everstake_swqos",
-            )
-            .map_err(|e| e.to_string())?
-            .await
-            .map_err(|e| e.to_string())?;
+async fn connect(
+    endpoint: &Endpoint,
+    client_config: &quinn::ClientConfig,
+    remote: SocketAddr,
+) -> Result<Connection, String> {
+    endpoint
+        .connect_with(client_config.clone(), remote, SERVER_NAME)
+        .map_err(|e| e.to_string())?
+        .await
+        .map_err(|e| e.to_string())
+}
 
-        Ok(Self {
-            _endpoint: Arc::new(endpoint),
-            connection,
-        })
+/// 在连接上开一个单向流写入交易
+async fn write_uni(connection: &Connection, raw_tx: &[u8]) -> Result<(), SendError> {
+    let mut send_stream = connection
+        .open_uni()
+        .await
+        .map_err(|e| SendError::Transport(format!("open uni stream failed: {}", e)))?;
+    send_stream
+        .write_all(raw_tx)
+        .await
+        .map_err(|e| SendError::Transport(format!("write stream failed: {}", e)))?;
+    send_stream
+        .finish()
+        .map_err(|e| SendError::Transport(format!("finish stream failed: {}", e)))?;
+    Ok(())
+}
+
+//Establish a connection to Everstake SWQoS Quic Endpoint
+impl EverStakeQuic {
+    pub const MIN_TIP_AMOUNT_TX: u64 = 0_000_500_000; // 单笔交易最低 tip
+    pub const DEFAULT_TPS: u64 = 1;
+    pub fn get_endpoint() -> String {
+        Self::endpoint_for(*REGION).to_string()
+    }
+
+    pub fn endpoint_for(region: Region) -> &'static str {
+        match region {
+            Region::Frankfurt => "64.130.57.62:11809",
+            Region::NewYork => "64.130.59.154:11809",
+            Region::Amsterdam => "74.118.140.197:11809",
+            Region::Tokyo => "208.91.107.171:11809",
+            _ => "64.130.57.62:11809",
+        }
+    }
+
+    pub async fn new() -> Result<Self, String> {
+        let keypair_base58_string = std::env::var("EVER_STAKE_QUIC_KEYPAIR").unwrap_or_default();
+        let keypair = Keypair::from_base58_string(&keypair_base58_string);
+        Self::init_with(&keypair, *REGION).await
     }
 
     pub async fn init_with(keypair: &Keypair, region: Region) -> Result<Self, String> {
+        Self::init_with_config(keypair, region, EverStakeQuicConfig::default()).await
+    }
+
+    /// 指定连接参数；连接断开后由后台任务按 `config` 的退避参数自动重连。
+    pub async fn init_with_config(
+        keypair: &Keypair,
+        region: Region,
+        config: EverStakeQuicConfig,
+    ) -> Result<Self, String> {
         let (cert, key) = new_dummy_x509_certificate(keypair);
 
         let mut crypto = rustls::ClientConfig::builder()
@@ -101,92 +290,107 @@ impl EverStakeQuic {
             .map_err(|_| "failed to convert rustls config into quinn crypto config")?;
         let mut client_config = quinn::ClientConfig::new(Arc::new(client_crypto));
         let mut transport_config = quinn::TransportConfig::default();
-        transport_config.keep_alive_interval(Some(Duration::from_secs(10)));
+        transport_config.keep_alive_interval(config.keep_alive_interval);
+        if let Some(timeout) = config.max_idle_timeout {
+            let timeout = IdleTimeout::try_from(timeout).map_err(|e| e.to_string())?;
+            transport_config.max_idle_timeout(Some(timeout));
+        }
         client_config.transport_config(Arc::new(transport_config));
 
         let mut endpoint = Endpoint::client("0.0.0.0:0".parse().map_err(|_| "fail to parse ip")?)
             .map_err(|e| e.to_string())?;
         endpoint.set_default_client_config(client_config.clone());
 
-        let quic_endpoint = match region {
-            Region::Frankfurt => "64.130.57.62:11809",
-            Region::NewYork => "64.130.59.154:11809",
-            Region::Amsterdam => "74.118.140.197:11809",
-            Region::Tokyo => "208.91.107.171:11809",
-            _ => "64.130.57.62:11809",
-        };
+        let remote: SocketAddr = Self::endpoint_for(region)
+            .parse()
+            .map_err(|_| "fail to get endpoint")?;
+        let connection = connect(&endpoint, &client_config, remote).await?;
+
+        let inner = Arc::new(Inner {
+            endpoint,
+            client_config,
+            remote,
+            config,
+            connection: RwLock::new(connection),
+            reconnect_lock: tokio::sync::Mutex::new(()),
+            state: watch::channel(ConnectionState::Connected).0,
+            supervisor: Mutex::new(None),
+        });
+        let supervisor = tokio::spawn(supervise(Arc::downgrade(&inner)));
+        *inner.supervisor.lock().unwrap() = Some(supervisor);
+        Ok(Self { inner })
+    }
 
-        let connection = endpoint
-            .connect_with(
-                client_config,
-                quic_endpoint.parse().map_err(|_| "fail to get endpoint")?,
-                "everstake_swqos",
-            )
-            .map_err(|e| e.to_string())?
-            .await
-            .map_err(|e| e.to_string())?;
+    /// 当前连接状态
+    pub fn state(&self) -> ConnectionState {
+        self.inner.state.borrow().clone()
+    }
 
-        Ok(Self {
-            _endpoint: Arc::new(endpoint),
-            connection,
-        })
+    /// 订阅连接状态变化
+    pub fn watch_state(&self) -> watch::Receiver<ConnectionState> {
+        self.inner.state.subscribe()
     }
 
     // Send a transaction via quic using a unidirectional stream
-    pub async fn send_transaction(&self, transaction: &Transaction) -> Result<(), String> {
+    pub async fn send_transaction(&self, transaction: &Transaction) -> Result<(), SendError> {
         let signature = transaction
             .signatures
             .first()
             .expect("Transaction must have at least one signature");
-        let serialized_tx = bincode::serialize(transaction).map_err(|e| e.to_string())?;
+        let serialized_tx =
+            bincode::serialize(transaction).map_err(|e| SendError::Serialize(e.to_string()))?;
 
-        let mut send_stream = self
-            .connection
-            .open_uni()
-            .await
-            .map_err(|e| e.to_string())?;
-        send_stream
-            .write_all(&serialized_tx)
-            .await
-            .map_err(|e| e.to_string())?;
-        send_stream.finish().map_err(|e| e.to_string())?;
+        self.send_raw_transaction(&serialized_tx).await?;
 
         info!("Transaction {signature:?} has been sent");
         Ok(())
     }
 
     // 核心逻辑：只管发字节，不关心内容
-    pub async fn send_raw_transaction(&self, raw_tx: &[u8]) -> Result<(), String> {
-        // 如果你依然需要提取 signature 用于打印日志，可以只解析前 64 字节（Solana 签名在最前面）
-        // 或者直接跳过解析，只打印长度
-
-        let mut send_stream = self
-            .connection
-            .open_uni()
-            .await
-            .map_err(|e| e.to_string())?;
-        send_stream
-            .write_all(raw_tx)
-            .await
-            .map_err(|e| e.to_string())?;
-        send_stream.finish().map_err(|e| e.to_string())?;
-        Ok(())
+    // 连接已断开导致失败时，立即重连一次并重试，不等待后台退避
+    pub async fn send_raw_transaction(&self, raw_tx: &[u8]) -> Result<(), SendError> {
+        let connection = self.inner.connection();
+        match write_uni(&connection, raw_tx).await {
+            Err(e) if connection.close_reason().is_some() => {
+                debug!(
+                    "[EverStakeQuic] send failed on closed connection: {}, reconnecting",
+                    e
+                );
+                let connection = self.inner.reconnect(&connection).await?;
+                write_uni(&connection, raw_tx).await
+            }
+            result => result,
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl SendTxEncoded for EverStakeQuic {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("ever stake quic send: ", {
             // 只需要 Base64 解码一次
             let bytes = base64::prelude::BASE64_STANDARD
                 .decode(tx_base64)
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| SendError::Serialize(format!("base64 decode failed: {}", e)))?;
 
             // 直接发送解码后的字节，无需转成 Transaction 结构体
-            self.send_raw_transaction(&bytes)
-                .await
-                .map_err(|e| format!("Everstake Quic send error: {}", e))
+            self.send_tx_raw(&bytes).await
+        })
+    }
+
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
+        let started = Instant::now();
+        self.send_raw_transaction(tx_bytes).await.inspect_err(|e| {
+            log::error!("Everstake Quic send error: {}", e);
+        })?;
+        // QUIC 单向流没有应答，只能记录写入完成的耗时
+        Ok(SubmitReceipt {
+            platform: PlatformName::EverStake,
+            endpoint: self.inner.remote.to_string(),
+            latency: started.elapsed(),
+            http_status: None,
+            server_id: None,
+            raw: None,
         })
     }
 }
@@ -196,17 +400,17 @@ impl crate::platform_clients::BuildTx for EverStakeQuic {
         PlatformName::EverStake
     }
     fn get_tip_address(&self) -> Pubkey {
-        *EVER_STAKE_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| EVER_STAKE_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::EverStake, EVER_STAKE_TIP_ACCOUNTS)
     }
 
     fn get_min_tip_amount(&self) -> u64 {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        EVER_STAKE_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::EverStake, EVER_STAKE_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
     }
 }
 
@@ -216,44 +420,75 @@ impl fmt::Display for EverStakeQuic {
     }
 }
 
-// #[tokio::test]
-// async fn test_everstake_quic() -> Result<()> {
-//     rustls::crypto::CryptoProvider::install_default(rustls::crypto::ring::default_provider())
-//         .unwrap();
-//     let everstake_keypair = Keypair::from_base58_string(
-//         "g6uc977Rk5ZF4jFP5J2PwKt3qRF59ncg2oZVFLq4erP7aUJJAUY7hfjrLWU7BtHLbGqncJHckjFrFeNkRFuQXHP",
-//     );
-//     let payer_key_pair =
-//         <Keypair as solana_sdk::signer::EncodableKey>::read_from_file("./test2.json").unwrap();
-//     let everstake_quic = EverStakeQuic::new(&everstake_keypair).await?;
-//     println!("Connected to Everstake SWQoS Quic Endpoint");
-
-//     {
-//         // 构造交易然后用send_transaction发送就行了
-//         let recent_blockhash = crate::constants::JSON_RPC_CLIENT
-//             .get_latest_blockhash()
-//             .await
-//             .context("failed to get recent blockhash")?;
-//         let hash = crate::platform_clients::HashParam::Blockhash(recent_blockhash);
-
-//         let tx = everstake_quic
-//             .build_v0_tx(
-//                 &[],
-//                 &Arc::new(payer_key_pair),
-//                 &None,
-//                 &hash,
-//                 &(None, None),
-//                 &[],
-//                 None,
-//             )
-//             .unwrap();
-//         tx.send()
-//             .await
-//             .map(|res| println!("Everstake Quic tx sent: {:?}", res))
-//             .unwrap();
-//     }
-//     tokio::time::sleep(Duration::from_secs(5)).await;
-//     println!("Test completed after waiting for 5 seconds.");
-
-//     Ok(())
-// }
+#[tokio::test]
+async fn test_reconnect_with_backoff() {
+    let config = EverStakeQuicConfig {
+        reconnect_initial_backoff: Duration::from_millis(1),
+        reconnect_max_backoff: Duration::from_millis(4),
+        ..EverStakeQuicConfig::default()
+    };
+
+    let mut backoff = Backoff::new(&config);
+    let delays: Vec<_> = (0..4).map(|_| backoff.next_delay().as_millis()).collect();
+    assert_eq!(delays, vec![1, 2, 4, 4]);
+
+    // 前两次失败，第三次成功
+    let mut states = Vec::new();
+    let mut attempts = 0;
+    let reconnected = reconnect_with_backoff(
+        &config,
+        "closed".to_string(),
+        |state| {
+            states.push(state);
+            true
+        },
+        || {
+            attempts += 1;
+            let result = if attempts < 3 {
+                Err(SendError::Transport(format!("refused {}", attempts)))
+            } else {
+                Ok(())
+            };
+            async move { Some(result) }
+        },
+    )
+    .await;
+    assert!(reconnected);
+    assert_eq!(attempts, 3);
+    let reconnecting = |attempt, reason: &str| ConnectionState::Reconnecting {
+        attempt,
+        reason: reason.to_string(),
+    };
+    assert_eq!(
+        states,
+        vec![
+            reconnecting(0, "closed"),
+            reconnecting(1, "transport error: refused 1"),
+            reconnecting(2, "transport error: refused 2"),
+            ConnectionState::Connected,
+        ]
+    );
+
+    // 连接已被发送方换掉：reconnect 立即返回，状态回到 Connected
+    let mut states = Vec::new();
+    let reconnected = reconnect_with_backoff(
+        &config,
+        "closed".to_string(),
+        |state| {
+            states.push(state);
+            true
+        },
+        || async { Some(Ok(())) },
+    )
+    .await;
+    assert!(reconnected);
+    assert_eq!(
+        states,
+        vec![reconnecting(0, "closed"), ConnectionState::Connected]
+    );
+
+    // 客户端已释放时停止重连
+    assert!(
+        !reconnect_with_backoff(&config, "closed".to_string(), |_| true, || async { None }).await
+    );
+}
@@ -1,18 +1,17 @@
-use base64::Engine;
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::env;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
-use solana_sdk::signature::Signature;
-
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region, SendTxEncoded, SolTx};
+use crate::error::{SendError, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SendTxEncoded, SolTx, SubmitReceipt};
+use crate::tip_accounts;
 pub const FLASH_BLOCK_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("FLaShB3iXXTWE1vu9wQsChUKq3HFtpMAhb8kAh1pf1wi"),
     pubkey!("FLashhsorBmM9dLpuq6qATawcpqk1Y2aqaZfkd48iT3W"),
@@ -117,18 +116,15 @@ impl FlashBlock {
     }
 
     fn get_tip_address() -> Pubkey {
-        *FLASH_BLOCK_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| FLASH_BLOCK_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::FlashBlock, FLASH_BLOCK_TIP_ACCOUNTS)
     }
 }
 
 #[async_trait::async_trait]
 impl SendTxEncoded for FlashBlock {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("flash block send:", {
-            let request_body = match serde_json::to_string(&json!({
+            let request_body = serde_json::to_string(&json!({
                 "id": 1,
                 "jsonrpc": "2.0",
                 "method": "sendBundle",
@@ -136,12 +132,11 @@ impl SendTxEncoded for FlashBlock {
                     [tx_base64],
                     { "encoding": "base64" }
                 ]
-            })) {
-                Ok(body) => body,
-                Err(e) => return Err(format!("serde_json error: {}", e)),
-            };
+            }))
+            .map_err(|e| SendError::Serialize(format!("serde_json error: {}", e)))?;
             let url = format!("{}/", self.endpoint);
 
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -150,33 +145,37 @@ impl SendTxEncoded for FlashBlock {
                 .body(request_body)
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("flashblock send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             log::info!("flashblock raw response: {:?}", response);
             // 尝试用结构体解析响应
             match serde_json::from_str::<FlashBlockSendBundleResponse>(&response) {
                 Ok(resp_obj) => {
                     if let Some(result) = resp_obj.result {
                         log::info!("flashblock bundle id: {}", result);
-                        Ok(())
+                        Ok(SubmitReceipt {
+                            platform: PlatformName::FlashBlock,
+                            endpoint: url,
+                            latency,
+                            http_status: Some(status),
+                            server_id: Some(result),
+                            raw: Some(response),
+                        })
                     } else if let Some(err) = resp_obj.error {
-                        Err(format!("flashblock error: {}", err))
+                        Err(SendError::from_rpc_error(&err))
                     } else {
-                        Err(format!("flashblock unknown response: {}", response))
+                        Err(SendError::Parse(format!(
+                            "flashblock unknown response: {}",
+                            response
+                        )))
                     }
                 }
-                Err(e) => Err(format!(
+                Err(e) => Err(SendError::Parse(format!(
                     "flashblock response parse error: {}, raw: {}",
                     e, response
-                )),
+                ))),
             }
         })
     }
@@ -184,19 +183,13 @@ impl SendTxEncoded for FlashBlock {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendBundle for FlashBlock {
-    async fn send_bundle(&self, txs: &[SolTx]) -> Result<Vec<Signature>, String> {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
         // 将所有交易序列化并 base64 编码
         let mut encoded_txs = Vec::with_capacity(txs.len());
-        let mut sigs: Vec<Signature> = Vec::with_capacity(txs.len());
         for tx in txs {
-            let encode_tx = match bincode::serialize(tx) {
-                Ok(bytes) => base64::prelude::BASE64_STANDARD.encode(&bytes),
-                Err(e) => return Err(format!("bincode serialize error: {}", e)),
-            };
-            encoded_txs.push(encode_tx);
-            sigs.push(tx.sig());
+            encoded_txs.push(tx.to_base64()?);
         }
-        let request_body = match serde_json::to_string(&json!({
+        let request_body = serde_json::to_string(&json!({
             "id": 1,
             "jsonrpc": "2.0",
             "method": "sendBundle",
@@ -204,12 +197,12 @@ impl crate::platform_clients::SendBundle for FlashBlock {
                 encoded_txs,
                 { "encoding": "base64" }
             ]
-        })) {
-            Ok(body) => body,
-            Err(e) => return Err(format!("serde_json error: {}", e)),
-        };
+        }))
+        .map_err(|e| SendError::Serialize(format!("serde_json error: {}", e)))?;
         let url = format!("{}/", self.endpoint);
 
+        let started = Instant::now();
+
         let res = self
             .http_client
             .post(&url)
@@ -218,33 +211,37 @@ impl crate::platform_clients::SendBundle for FlashBlock {
             .body(request_body)
             .send()
             .await;
-        let response = match res {
-            Ok(resp) => match resp.text().await {
-                Ok(text) => text,
-                Err(e) => return Err(format!("response text error: {}", e)),
-            },
-            Err(e) => {
-                log::error!("send error: {:?}", e);
-                return Err(format!("send error: {}", e));
-            }
-        };
+        let (status, response) = read_http_response(res).await.inspect_err(|e| {
+            log::error!("flashblock bundle send error: {}", e);
+        })?;
+        let latency = started.elapsed();
         log::info!("flashblock raw response: {:?}", response);
         // 尝试用结构体解析响应
         match serde_json::from_str::<FlashBlockSendBundleResponse>(&response) {
             Ok(resp_obj) => {
                 if let Some(result) = resp_obj.result {
                     log::info!("flashblock bundle id: {}", result);
-                    Ok(sigs)
+                    Ok(SubmitReceipt {
+                        platform: PlatformName::FlashBlock,
+                        endpoint: url,
+                        latency,
+                        http_status: Some(status),
+                        server_id: Some(result),
+                        raw: Some(response),
+                    })
                 } else if let Some(err) = resp_obj.error {
-                    Err(format!("flashblock error: {}", err))
+                    Err(SendError::from_rpc_error(&err))
                 } else {
-                    Err(format!("flashblock unknown response: {}", response))
+                    Err(SendError::Parse(format!(
+                        "flashblock unknown response: {}",
+                        response
+                    )))
                 }
             }
-            Err(e) => Err(format!(
+            Err(e) => Err(SendError::Parse(format!(
                 "flashblock response parse error: {}, raw: {}",
                 e, response
-            )),
+            ))),
         }
     }
 }
@@ -260,7 +257,10 @@ impl crate::platform_clients::BuildTx for FlashBlock {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        FLASH_BLOCK_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::FlashBlock, FLASH_BLOCK_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
     }
     // 使用默认实现，无需重写 build_tx
 }
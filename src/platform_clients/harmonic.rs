@@ -5,7 +5,7 @@
 //! 1. **Tips = CU price，无需 SOL 转账**
 //!    Harmonic 的 tip 就是交易的 compute unit price（priority fee），
 //!    不需要额外的 SOL 转账指令。`uses_tip_transfer()` 返回 `false`，
-//!    `build_v0_tx` 调用时应显式传 `tip = Some(0)` 跳过 tip 指令。
+//!    `build_v0_tx` 传入的任何 `TipStrategy` 都不会写入 tip 指令。
 //!
 //! 2. **Role = SEARCHER = 3**（Jito 是 1）
 //!    如果你想复用 Jito protos，使用 `SHREDSTREAM_SUBSCRIBER = 3`。
@@ -13,31 +13,29 @@
 //!
 //! 3. **所有区域并发发送**
 //!    官方建议同时发往所有 endpoint 以获得最低延迟。
+//!
+//! 4. **连接与 token 复用**
+//!    每个区域一个 `BlockEngineSession`：长连接 + access token 缓存，临近过期用
+//!    `RefreshAccessToken` 续期，稳态下一次发送只有一个 `SendBundle` RPC。
+//!
+//! bundle 结果订阅见 `harmonic_bundle`。
 
+use crate::error::SendError;
+use crate::platform_clients::block_engine::{
+    BlockEngineSession, bundle_of, check_bundle, classify_error, with_bearer,
+};
 use crate::platform_clients::harmonic_proto::{
-    auth::{
-        GenerateAuthChallengeRequest, GenerateAuthTokensRequest, Role,
-        auth_service_client::AuthServiceClient,
-    },
-    bundle::Bundle,
-    packet::{Meta, Packet, PacketFlags},
-    searcher::{SendBundleRequest, searcher_service_client::SearcherServiceClient},
-    shared::Header,
+    auth::Role, bundle::Bundle, searcher::SendBundleRequest,
 };
-use crate::platform_clients::{PlatformName, Region};
+use crate::platform_clients::{PlatformName, Region, SolTx, SubmitReceipt};
 
-use anyhow::{Context, anyhow};
+use anyhow::Context;
 use base64::Engine;
 use log::{error, info};
-use prost_types::Timestamp;
-use solana_sdk::transaction::VersionedTransaction;
-use solana_sdk::{pubkey, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use solana_sdk::{pubkey, pubkey::Pubkey, signature::Keypair};
 use std::fmt;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tonic::Request;
-use tonic::metadata::MetadataValue;
-use tonic::transport::{Channel, ClientTlsConfig};
+use std::time::Instant;
 
 // ── 端点 ─────────────────────────────────────────────────────────────────────
 
@@ -50,19 +48,24 @@ pub const HARMONIC_BE_ENDPOINTS: &[&str] = &[
     "https://sgp.be.harmonic.gg", // Singapore
 ];
 
+/// 单个区域接受 bundle 后返回的 uuid
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegionUuid {
+    pub endpoint: String,
+    pub uuid: String,
+}
+
 // ── HarmonicBlockEngine ───────────────────────────────────────────────────────
 
 /// Harmonic Block Engine 客户端。
 ///
 /// **Tip 说明**：Harmonic 的竞价通过 CU price 完成，不需要额外的 SOL 转账指令。
-/// 在 `build_v0_tx` 调用时应传 `tip = Some(0)` 以跳过 tip 转账指令，
+/// `build_v0_tx` 不会写入 tip 转账指令（与传入的 `TipStrategy` 无关），
 /// 竞价效果由 `cu_price` 参数决定。
 #[derive(Clone)]
 pub struct HarmonicBlockEngine {
-    /// 并发发送到的所有 endpoint（官方建议全区域同发）
-    endpoints: Vec<String>,
-    /// 已被 Harmonic whitelist 的 searcher keypair
-    searcher: Arc<Keypair>,
+    /// 并发发送到的所有 endpoint 的会话（官方建议全区域同发），clone 之间共享 token 与连接
+    pub(crate) sessions: Vec<Arc<BlockEngineSession>>,
 }
 
 impl fmt::Display for HarmonicBlockEngine {
@@ -73,54 +76,113 @@ impl fmt::Display for HarmonicBlockEngine {
 
 impl HarmonicBlockEngine {
     /// Harmonic 不收 tip，竞价靠 CU price，此处返回 0。
-    /// 外部调用时应传 `tip = Some(0)` 确保不生成 tip 转账指令。
+    /// `uses_tip_transfer()` 为 `false`，传入的任何 `TipStrategy` 都不会写入 tip 指令。
     pub const MIN_TIP_AMOUNT_TX: u64 = 0;
 
     /// 默认使用全部 endpoint（官方推荐）。
     pub fn init_with(searcher: Arc<Keypair>) -> Self {
-        Self {
-            endpoints: HARMONIC_BE_ENDPOINTS
+        Self::init_with_endpoints(
+            searcher,
+            HARMONIC_BE_ENDPOINTS
                 .iter()
                 .map(|s| s.to_string())
                 .collect(),
-            searcher,
-        }
+        )
     }
 
     /// 指定部分 endpoint（用于测试或按需选择区域）。
     pub fn init_with_endpoints(searcher: Arc<Keypair>, endpoints: Vec<String>) -> Self {
         Self {
-            endpoints,
-            searcher,
+            sessions: endpoints
+                .into_iter()
+                .map(|ep| {
+                    Arc::new(BlockEngineSession::new(
+                        ep,
+                        Arc::clone(&searcher),
+                        // Role::Searcher = 3，Harmonic 专用，与 Jito 的 1 不同
+                        Role::Searcher,
+                    ))
+                })
+                .collect(),
         }
     }
 
-    /// 将序列化后的交易字节封装成 Harmonic bundle 并发往所有 endpoint。
-    /// 并发发送，任一成功即视为整体成功（其余结果仍等待并记录日志）。
-    async fn send_bundle_bytes(&self, tx_bytes: Vec<u8>) -> Result<(), String> {
-        let packet_size = tx_bytes.len() as u64;
+    /// 提前为所有区域建立连接并完成认证，避免首笔发送承担握手开销。
+    /// 返回认证成功的区域数。
+    pub async fn warm_up(&self) -> usize {
+        let handles: Vec<_> = self
+            .sessions
+            .iter()
+            .map(|session| {
+                let session = Arc::clone(session);
+                tokio::spawn(async move {
+                    let result = session.access_token().await;
+                    if let Err(e) = &result {
+                        error!(
+                            "[HarmonicBlockEngine] {} warm up failed: {:#}",
+                            region_name(session.endpoint()),
+                            e
+                        );
+                    }
+                    result.is_ok()
+                })
+            })
+            .collect();
+        let mut ready = 0;
+        for handle in handles {
+            if matches!(handle.await, Ok(true)) {
+                ready += 1;
+            }
+        }
+        ready
+    }
+
+    /// 多笔交易按顺序组成一个 bundle 发往所有 endpoint，额外返回各区域的 uuid。
+    pub async fn send_bundle_by_region(
+        &self,
+        txs: &[SolTx],
+    ) -> Result<(SubmitReceipt, Vec<RegionUuid>), SendError> {
+        let mut tx_bytes = Vec::with_capacity(txs.len());
+        for tx in txs {
+            tx_bytes.push(tx.to_bytes()?);
+        }
+        self.send_bundle_bytes(tx_bytes).await
+    }
 
-        let mut handles = Vec::with_capacity(self.endpoints.len());
-        for endpoint in &self.endpoints {
-            let endpoint = endpoint.clone();
-            let tx_bytes = tx_bytes.clone();
-            let searcher = Arc::clone(&self.searcher);
+    /// 将序列化后的交易字节按顺序封装成 Harmonic bundle 并发往所有 endpoint。
+    /// 并发发送，任一成功即视为整体成功（其余结果仍等待并记录日志）。
+    /// 回执的 `server_id` 为第一个成功区域返回的 uuid，`raw` 汇总各区域的 uuid。
+    async fn send_bundle_bytes(
+        &self,
+        txs: Vec<Vec<u8>>,
+    ) -> Result<(SubmitReceipt, Vec<RegionUuid>), SendError> {
+        check_bundle(&txs)?;
+        let bundle = bundle_of(txs).map_err(|e| SendError::Serialize(format!("{:#}", e)))?;
+        let started = Instant::now();
+
+        let mut handles = Vec::with_capacity(self.sessions.len());
+        for session in &self.sessions {
+            let session = Arc::clone(session);
+            let bundle = bundle.clone();
 
             handles.push(tokio::spawn(async move {
-                let result = send_to_endpoint(&endpoint, &searcher, tx_bytes, packet_size).await;
-                (endpoint, result)
+                let result = send_to_endpoint(&session, bundle).await;
+                (session.endpoint().to_string(), result)
             }));
         }
 
-        let mut any_ok = false;
+        // 按 endpoint 顺序记录成功的区域
+        let mut accepted: Vec<RegionUuid> = Vec::new();
+        let mut first_err: Option<SendError> = None;
         for handle in handles {
             match handle.await {
                 Ok((ep, Ok(uuid))) => {
                     info!("[HarmonicBlockEngine] {} → uuid={}", region_name(&ep), uuid);
-                    any_ok = true;
+                    accepted.push(RegionUuid { endpoint: ep, uuid });
                 }
                 Ok((ep, Err(e))) => {
                     error!("[HarmonicBlockEngine] {} failed: {:#}", region_name(&ep), e);
+                    first_err.get_or_insert_with(|| classify_error(&e));
                 }
                 Err(e) => {
                     error!("[HarmonicBlockEngine] task join error: {}", e);
@@ -128,10 +190,31 @@ impl HarmonicBlockEngine {
             }
         }
 
-        if any_ok {
-            Ok(())
+        if !accepted.is_empty() {
+            let endpoint = accepted
+                .iter()
+                .map(|r| r.endpoint.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            let raw = accepted
+                .iter()
+                .map(|r| format!("{}={}", region_name(&r.endpoint), r.uuid))
+                .collect::<Vec<_>>()
+                .join(",");
+            let receipt = SubmitReceipt {
+                platform: PlatformName::Harmonic,
+                endpoint,
+                latency: started.elapsed(),
+                http_status: None,
+                server_id: accepted.first().map(|r| r.uuid.clone()),
+                raw: Some(raw),
+            };
+            Ok((receipt, accepted))
         } else {
-            Err("all Harmonic endpoints failed".to_string())
+            // 全部区域失败时返回第一个区域的错误，便于调用方判断是否重试
+            Err(first_err.unwrap_or_else(|| {
+                SendError::Transport("all Harmonic endpoints failed".to_string())
+            }))
         }
     }
 }
@@ -140,17 +223,43 @@ impl HarmonicBlockEngine {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for HarmonicBlockEngine {
-    /// 接收 base64 编码的交易，反序列化后通过 gRPC bundle 发送。
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    /// 接收 base64 编码的交易，解码后通过 gRPC bundle 发送。
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         let tx_bytes = base64::prelude::BASE64_STANDARD
             .decode(tx_base64)
-            .map_err(|e| format!("base64 decode failed: {}", e))?;
+            .map_err(|e| SendError::Serialize(format!("base64 decode failed: {}", e)))?;
+        self.send_bundle_bytes(vec![tx_bytes])
+            .await
+            .map(|(receipt, _)| receipt)
+    }
+
+    /// 序列化后的交易字节直接装入 Packet，不再做 base64 / bincode 往返。
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
+        self.send_bundle_bytes(vec![tx_bytes.to_vec()])
+            .await
+            .map(|(receipt, _)| receipt)
+    }
+}
 
-        // 验证反序列化（确保 tx_bytes 是合法的 VersionedTransaction）
-        let _ = bincode::deserialize::<VersionedTransaction>(&tx_bytes)
-            .map_err(|e| format!("deserialize tx failed: {}", e))?;
+#[async_trait::async_trait]
+impl crate::platform_clients::SendBundle for HarmonicBlockEngine {
+    /// 多笔交易按顺序装入同一个 bundle，全区域并发发送。
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        self.send_bundle_by_region(txs)
+            .await
+            .map(|(receipt, _)| receipt)
+    }
+}
 
-        self.send_bundle_bytes(tx_bytes).await
+impl crate::platform_clients::BuildBundle for HarmonicBlockEngine {
+    fn build_bundle<'a>(
+        &'a self,
+        txs: &[SolTx],
+    ) -> crate::platform_clients::BundleEnvelope<'a, HarmonicBlockEngine> {
+        crate::platform_clients::BundleEnvelope {
+            txs: txs.to_vec(),
+            sender: self,
+        }
     }
 }
 
@@ -178,110 +287,30 @@ impl crate::platform_clients::BuildTx for HarmonicBlockEngine {
 
 // ── 内部 gRPC 函数 ────────────────────────────────────────────────────────────
 
-/// 向单个 endpoint 认证并发送 bundle。
-async fn send_to_endpoint(
-    endpoint: &str,
-    searcher: &Keypair,
-    tx_bytes: Vec<u8>,
-    packet_size: u64,
-) -> anyhow::Result<String> {
-    let token = authenticate(endpoint, searcher).await?;
-    let channel = connect(endpoint).await?;
-    let mut client = SearcherServiceClient::new(channel);
-
-    let mut req = Request::new(SendBundleRequest {
-        bundle: Some(Bundle {
-            header: Some(Header {
-                ts: Some(now_ts()?),
-            }),
-            packets: vec![Packet {
-                data: tx_bytes,
-                meta: Some(Meta {
-                    size: packet_size,
-                    addr: String::new(),
-                    port: 0,
-                    flags: Some(PacketFlags {
-                        discard: false,
-                        forwarded: false,
-                        repair: false,
-                        simple_vote_tx: false,
-                        tracer_packet: false,
-                        from_staked_node: false,
-                    }),
-                    sender_stake: 0,
-                }),
-            }],
-        }),
-    });
-
-    let bearer = MetadataValue::try_from(format!("Bearer {token}"))
-        .context("build bearer metadata failed")?;
-    req.metadata_mut().insert("authorization", bearer);
-
-    let resp = client
-        .send_bundle(req)
-        .await
-        .context("SendBundle gRPC failed")?
-        .into_inner();
-
-    Ok(resp.uuid)
-}
-
-/// challenge-response 认证，返回 access token。
-async fn authenticate(endpoint: &str, searcher: &Keypair) -> anyhow::Result<String> {
-    let channel = connect(endpoint).await?;
-    let mut client = AuthServiceClient::new(channel);
-    let pubkey = searcher.pubkey();
-
-    // 1. 请求 challenge（Role::Searcher = 3，Harmonic 专用，与 Jito 的 1 不同）
-    let challenge_resp = client
-        .generate_auth_challenge(GenerateAuthChallengeRequest {
-            role: Role::Searcher as i32,
-            pubkey: pubkey.to_bytes().to_vec(),
-        })
-        .await
-        .context("GenerateAuthChallenge failed")?
-        .into_inner();
-
-    // 2. 签名 "{pubkey}-{challenge}"
-    let challenge = format!("{}-{}", pubkey, challenge_resp.challenge);
-    let signed = searcher.sign_message(challenge.as_bytes());
-
-    // 3. 换取 access token
-    let tokens = client
-        .generate_auth_tokens(GenerateAuthTokensRequest {
-            challenge,
-            client_pubkey: pubkey.to_bytes().to_vec(),
-            signed_challenge: signed.as_ref().to_vec(),
+/// 通过已认证的会话发送 bundle，token 有效时只需一次 SendBundle。
+async fn send_to_endpoint(session: &BlockEngineSession, bundle: Bundle) -> anyhow::Result<String> {
+    session
+        .call(|mut client, token| {
+            let bundle = bundle.clone();
+            async move {
+                let req = with_bearer(
+                    SendBundleRequest {
+                        bundle: Some(bundle),
+                    },
+                    &token,
+                )?;
+                let resp = client
+                    .send_bundle(req)
+                    .await
+                    .context("SendBundle gRPC failed")?
+                    .into_inner();
+                Ok(resp.uuid)
+            }
         })
         .await
-        .context("GenerateAuthTokens failed")?
-        .into_inner();
-
-    tokens
-        .access_token
-        .ok_or_else(|| anyhow!("auth response missing access_token"))
-        .map(|t| t.value)
-}
-
-async fn connect(endpoint: &str) -> anyhow::Result<Channel> {
-    let ep = Channel::from_shared(endpoint.to_string())
-        .with_context(|| format!("invalid endpoint: {endpoint}"))?
-        .tls_config(ClientTlsConfig::new().with_enabled_roots())?;
-    ep.connect().await.context("connect gRPC endpoint failed")
-}
-
-fn now_ts() -> anyhow::Result<Timestamp> {
-    let d = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .context("system clock error")?;
-    Ok(Timestamp {
-        seconds: d.as_secs() as i64,
-        nanos: d.subsec_nanos() as i32,
-    })
 }
 
-fn region_name(endpoint: &str) -> &str {
+pub(crate) fn region_name(endpoint: &str) -> &str {
     endpoint
         .trim_start_matches("https://")
         .split('.')
@@ -0,0 +1,417 @@
+//! Harmonic bundle 结果订阅
+//!
+//! 每个区域各开一条 `SubscribeBundleResults` 流（断开后自动重连），
+//! 按 `send_bundle_by_region` 返回的 `RegionUuid` 匹配结果，输出带类型的事件。
+//! 结果可能先于 `track` 到达，未跟踪的结果会暂存一段时间，`track` 时补发。
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ahash::{AHashMap, AHashSet};
+use anyhow::Context;
+use log::{info, warn};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::platform_clients::block_engine::{BlockEngineSession, with_bearer};
+use crate::platform_clients::harmonic::{HarmonicBlockEngine, RegionUuid, region_name};
+use crate::platform_clients::harmonic_proto::bundle::{
+    BundleResult, DroppedReason, bundle_result, rejected,
+};
+use crate::platform_clients::harmonic_proto::searcher::SubscribeBundleResultsRequest;
+
+/// 暂存的未跟踪结果上限（按区域 + uuid 计），已跟踪的 bundle 同样以此为上限，
+/// 一直收不到终态结果的 bundle 超出后按跟踪顺序淘汰
+const MAX_PENDING: usize = 1024;
+/// 结果流断开后的重连间隔
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// bundle 被拒绝的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleRejection {
+    StateAuctionBid {
+        auction_id: String,
+        simulated_bid_lamports: u64,
+        msg: Option<String>,
+    },
+    WinningBatchBid {
+        auction_id: String,
+        simulated_bid_lamports: u64,
+        msg: Option<String>,
+    },
+    SimulationFailure {
+        tx_signature: String,
+        msg: Option<String>,
+    },
+    InternalError(String),
+    Dropped(String),
+    /// 服务端未给出原因
+    Unknown,
+}
+
+/// bundle 被丢弃的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropReason {
+    BlockhashExpired,
+    PartiallyProcessed,
+    NotFinalized,
+    /// proto 中未定义的原因，保留原始值
+    Unknown(i32),
+}
+
+impl From<i32> for DropReason {
+    fn from(raw: i32) -> Self {
+        match DroppedReason::try_from(raw) {
+            Ok(DroppedReason::BlockhashExpired) => DropReason::BlockhashExpired,
+            Ok(DroppedReason::PartiallyProcessed) => DropReason::PartiallyProcessed,
+            Ok(DroppedReason::NotFinalized) => DropReason::NotFinalized,
+            Err(_) => DropReason::Unknown(raw),
+        }
+    }
+}
+
+/// 单条 bundle 结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleOutcome {
+    Accepted {
+        slot: u64,
+        validator: String,
+    },
+    Rejected(BundleRejection),
+    Processed {
+        slot: u64,
+        validator: String,
+        bundle_index: u64,
+    },
+    Finalized,
+    Dropped(DropReason),
+}
+
+impl BundleOutcome {
+    /// 之后不会再有该 bundle 的结果
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            BundleOutcome::Rejected(_) | BundleOutcome::Finalized | BundleOutcome::Dropped(_)
+        )
+    }
+
+    fn from_proto(result: bundle_result::Result) -> Self {
+        use bundle_result::Result as R;
+        match result {
+            R::Accepted(a) => BundleOutcome::Accepted {
+                slot: a.slot,
+                validator: a.validator_identity,
+            },
+            R::Rejected(r) => BundleOutcome::Rejected(match r.reason {
+                Some(rejected::Reason::StateAuctionBidRejected(r)) => {
+                    BundleRejection::StateAuctionBid {
+                        auction_id: r.auction_id,
+                        simulated_bid_lamports: r.simulated_bid_lamports,
+                        msg: r.msg,
+                    }
+                }
+                Some(rejected::Reason::WinningBatchBidRejected(r)) => {
+                    BundleRejection::WinningBatchBid {
+                        auction_id: r.auction_id,
+                        simulated_bid_lamports: r.simulated_bid_lamports,
+                        msg: r.msg,
+                    }
+                }
+                Some(rejected::Reason::SimulationFailure(r)) => {
+                    BundleRejection::SimulationFailure {
+                        tx_signature: r.tx_signature,
+                        msg: r.msg,
+                    }
+                }
+                Some(rejected::Reason::InternalError(r)) => BundleRejection::InternalError(r.msg),
+                Some(rejected::Reason::DroppedBundle(r)) => BundleRejection::Dropped(r.msg),
+                None => BundleRejection::Unknown,
+            }),
+            R::Processed(p) => BundleOutcome::Processed {
+                slot: p.slot,
+                validator: p.validator_identity,
+                bundle_index: p.bundle_index,
+            },
+            R::Finalized(_) => BundleOutcome::Finalized,
+            R::Dropped(d) => BundleOutcome::Dropped(DropReason::from(d.reason)),
+        }
+    }
+}
+
+/// 匹配到已发送 bundle 的结果事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleResultEvent {
+    /// 返回该结果的区域及其 uuid
+    pub bundle: RegionUuid,
+    pub outcome: BundleOutcome,
+}
+
+/// 已跟踪的 bundle 与尚未跟踪的暂存结果，均按 `(endpoint, uuid)` 索引
+#[derive(Default)]
+struct Matcher {
+    tracked: AHashSet<RegionUuid>,
+    tracked_order: VecDeque<RegionUuid>,
+    pending: AHashMap<RegionUuid, Vec<BundleResultEvent>>,
+    pending_order: VecDeque<RegionUuid>,
+}
+
+impl Matcher {
+    /// 处理一条结果，已跟踪时返回事件，否则暂存
+    fn on_result(&mut self, endpoint: &str, result: BundleResult) -> Option<BundleResultEvent> {
+        let outcome = BundleOutcome::from_proto(result.result?);
+        let event = BundleResultEvent {
+            bundle: RegionUuid {
+                endpoint: endpoint.to_string(),
+                uuid: result.bundle_id,
+            },
+            outcome,
+        };
+        let key = &event.bundle;
+        if self.tracked.contains(key) {
+            if event.outcome.is_terminal() {
+                self.tracked.remove(key);
+                self.tracked_order.retain(|k| k != key);
+            }
+            return Some(event);
+        }
+
+        if !self.pending.contains_key(key) {
+            if self.pending_order.len() >= MAX_PENDING
+                && let Some(oldest) = self.pending_order.pop_front()
+            {
+                self.pending.remove(&oldest);
+            }
+            self.pending_order.push_back(key.clone());
+        }
+        self.pending.entry(key.clone()).or_default().push(event);
+        None
+    }
+
+    /// 开始跟踪一组区域 uuid，返回此前已到达的结果
+    fn track(&mut self, bundles: &[RegionUuid]) -> Vec<BundleResultEvent> {
+        let mut ready = Vec::new();
+        for bundle in bundles {
+            let events = self.pending.remove(bundle).unwrap_or_default();
+            self.pending_order.retain(|key| key != bundle);
+            let terminal = events.iter().any(|e| e.outcome.is_terminal());
+            ready.extend(events);
+            if !terminal && self.tracked.insert(bundle.clone()) {
+                if self.tracked_order.len() >= MAX_PENDING
+                    && let Some(oldest) = self.tracked_order.pop_front()
+                {
+                    self.tracked.remove(&oldest);
+                }
+                self.tracked_order.push_back(bundle.clone());
+            }
+        }
+        ready
+    }
+}
+
+/// 全区域的 bundle 结果订阅，drop 时关闭所有结果流
+pub struct BundleResultSubscription {
+    matcher: Arc<Mutex<Matcher>>,
+    tx: mpsc::UnboundedSender<BundleResultEvent>,
+    rx: mpsc::UnboundedReceiver<BundleResultEvent>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl BundleResultSubscription {
+    /// 跟踪 `send_bundle_by_region` 返回的 uuid，之后这些 bundle 的结果会出现在 `recv` 中
+    pub fn track(&self, bundles: &[RegionUuid]) {
+        let ready = self.matcher.lock().unwrap().track(bundles);
+        for event in ready {
+            let _ = self.tx.send(event);
+        }
+    }
+
+    /// 下一条已跟踪 bundle 的结果
+    pub async fn recv(&mut self) -> Option<BundleResultEvent> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for BundleResultSubscription {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl HarmonicBlockEngine {
+    /// 订阅所有区域的 bundle 结果。应在发送前订阅，避免错过早到的结果。
+    pub fn subscribe_bundle_results(&self) -> BundleResultSubscription {
+        let matcher = Arc::new(Mutex::new(Matcher::default()));
+        let (tx, rx) = mpsc::unbounded_channel();
+        let tasks = self
+            .sessions
+            .iter()
+            .map(|session| {
+                tokio::spawn(run_results_stream(
+                    Arc::clone(session),
+                    Arc::clone(&matcher),
+                    tx.clone(),
+                ))
+            })
+            .collect();
+        BundleResultSubscription {
+            matcher,
+            tx,
+            rx,
+            tasks,
+        }
+    }
+}
+
+/// 单个区域的结果流，断开后按固定间隔重连
+async fn run_results_stream(
+    session: Arc<BlockEngineSession>,
+    matcher: Arc<Mutex<Matcher>>,
+    tx: mpsc::UnboundedSender<BundleResultEvent>,
+) {
+    let region = region_name(session.endpoint()).to_string();
+    loop {
+        let stream = session
+            .call(|mut client, token| async move {
+                let stream = client
+                    .subscribe_bundle_results(with_bearer(
+                        SubscribeBundleResultsRequest {},
+                        &token,
+                    )?)
+                    .await
+                    .context("SubscribeBundleResults gRPC failed")?
+                    .into_inner();
+                Ok(stream)
+            })
+            .await;
+        match stream {
+            Ok(mut stream) => {
+                info!("[HarmonicBlockEngine] {} bundle results subscribed", region);
+                loop {
+                    match stream.message().await {
+                        Ok(Some(result)) => {
+                            let event = matcher
+                                .lock()
+                                .unwrap()
+                                .on_result(session.endpoint(), result);
+                            if let Some(event) = event
+                                && tx.send(event).is_err()
+                            {
+                                return;
+                            }
+                        }
+                        Ok(None) => {
+                            warn!(
+                                "[HarmonicBlockEngine] {} bundle results stream ended",
+                                region
+                            );
+                            break;
+                        }
+                        Err(status) => {
+                            warn!(
+                                "[HarmonicBlockEngine] {} bundle results stream error: {}",
+                                region, status
+                            );
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!(
+                "[HarmonicBlockEngine] {} subscribe bundle results failed: {:#}",
+                region, e
+            ),
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+}
+
+#[test]
+fn test_bundle_result_matching() {
+    use crate::platform_clients::harmonic_proto::bundle::{Accepted, Dropped, Finalized};
+
+    let result = |uuid: &str, r: bundle_result::Result| BundleResult {
+        bundle_id: uuid.to_string(),
+        result: Some(r),
+    };
+    let accepted = || {
+        bundle_result::Result::Accepted(Accepted {
+            slot: 7,
+            validator_identity: "v".to_string(),
+        })
+    };
+    let region = |uuid: &str| RegionUuid {
+        endpoint: "https://fra.be.harmonic.gg".to_string(),
+        uuid: uuid.to_string(),
+    };
+    let mut m = Matcher::default();
+
+    // 结果先于 track 到达：暂存，track 时补发
+    assert!(
+        m.on_result("https://fra.be.harmonic.gg", result("a", accepted()))
+            .is_none()
+    );
+    let ready = m.track(&[region("a"), region("b")]);
+    assert_eq!(ready.len(), 1);
+    assert_eq!(
+        ready[0].outcome,
+        BundleOutcome::Accepted {
+            slot: 7,
+            validator: "v".to_string()
+        }
+    );
+
+    // 已跟踪：直接输出，终态后停止跟踪
+    let ev = m
+        .on_result(
+            "https://fra.be.harmonic.gg",
+            result("b", bundle_result::Result::Finalized(Finalized {})),
+        )
+        .unwrap();
+    assert_eq!(ev.bundle, region("b"));
+    assert!(ev.outcome.is_terminal());
+    assert!(!m.tracked.contains(&region("b")));
+
+    // 同一 uuid 来自未跟踪的区域时不输出
+    m.track(&[region("d")]);
+    assert!(
+        m.on_result("https://ams.be.harmonic.gg", result("d", accepted()))
+            .is_none()
+    );
+    assert!(
+        m.on_result("https://fra.be.harmonic.gg", result("d", accepted()))
+            .is_some()
+    );
+
+    // 未跟踪的 uuid 不输出
+    let dropped = bundle_result::Result::Dropped(Dropped {
+        reason: DroppedReason::PartiallyProcessed as i32,
+    });
+    assert!(
+        m.on_result("https://fra.be.harmonic.gg", result("c", dropped))
+            .is_none()
+    );
+    let ready = m.track(&[region("c")]);
+    assert_eq!(
+        ready[0].outcome,
+        BundleOutcome::Dropped(DropReason::PartiallyProcessed)
+    );
+    assert!(!m.tracked.contains(&region("c")));
+    assert_eq!(DropReason::from(42), DropReason::Unknown(42));
+
+    // 一直没有终态结果的 bundle 超过上限后淘汰最早跟踪的
+    let mut m = Matcher::default();
+    let bundles: Vec<_> = (0..=MAX_PENDING).map(|i| region(&i.to_string())).collect();
+    m.track(&bundles);
+    assert_eq!(m.tracked.len(), MAX_PENDING);
+    assert_eq!(m.tracked_order.len(), MAX_PENDING);
+    assert!(!m.tracked.contains(&bundles[0]));
+    assert!(m.tracked.contains(&bundles[MAX_PENDING]));
+    assert!(
+        m.on_result("https://fra.be.harmonic.gg", result("0", accepted()))
+            .is_none()
+    );
+}
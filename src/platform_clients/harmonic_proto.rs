@@ -1,4 +1,4 @@
-//! Harmonic gRPC proto 生成模块（tonic 编译）
+//! Block Engine gRPC proto 生成模块（tonic 编译，Harmonic / Jito 共用）
 
 pub mod auth {
     tonic::include_proto!("auth");
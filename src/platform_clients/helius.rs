@@ -5,16 +5,18 @@ impl fmt::Display for Helius {
     }
 }
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SubmitReceipt};
+use crate::tip_accounts;
 
 // helius 小费地址
 pub const HELIUS_TIP_ACCOUNTS: &[Pubkey] = &[
@@ -108,8 +110,9 @@ impl Helius {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for Helius {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("helius send: ", {
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&self.endpoint)
@@ -130,28 +133,27 @@ impl crate::platform_clients::SendTxEncoded for Helius {
                 }))
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("helius send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             info!("helius: {}", response);
-            Ok(())
+            let signature = parse_json_rpc_result(&response)?;
+            Ok(SubmitReceipt {
+                platform: PlatformName::Helius,
+                endpoint: self.endpoint.clone(),
+                latency,
+                http_status: Some(status),
+                server_id: signature.as_str().map(str::to_string),
+                raw: Some(response),
+            })
         })
     }
 }
 
 impl crate::platform_clients::BuildTx for Helius {
     fn get_tip_address(&self) -> Pubkey {
-        *HELIUS_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| HELIUS_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Helius, HELIUS_TIP_ACCOUNTS)
     }
     fn platform(&self) -> PlatformName {
         PlatformName::Helius
@@ -161,7 +163,10 @@ impl crate::platform_clients::BuildTx for Helius {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        HELIUS_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Helius, HELIUS_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
     }
     // 使用默认实现，无需重写 build_tx
 }
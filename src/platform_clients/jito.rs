@@ -12,20 +12,19 @@ impl fmt::Display for Jito {
         write!(f, "Jito")
     }
 }
-use base64::Engine;
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
-use solana_sdk::signature::Signature;
-
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region, SolTx};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SolTx, SubmitReceipt};
+use crate::tip_accounts;
 pub const JITO_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
     pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
@@ -98,19 +97,16 @@ impl Jito {
     }
 
     fn get_tip_address() -> Pubkey {
-        *JITO_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| JITO_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Jito, JITO_TIP_ACCOUNTS)
     }
 }
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for Jito {
     /// 直接接收 base64 编码后的交易数据并发送
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("jito send:", {
-            let request_body = match serde_json::to_string(&json!({
+            let request_body = serde_json::to_string(&json!({
                 "id": 1,
                 "jsonrpc": "2.0",
                 "method": "sendBundle",
@@ -118,10 +114,8 @@ impl crate::platform_clients::SendTxEncoded for Jito {
                     [tx_base64],
                     { "encoding": "base64" }
                 ]
-            })) {
-                Ok(body) => body,
-                Err(e) => return Err(format!("serde_json error: {}", e)),
-            };
+            }))
+            .map_err(|e| SendError::Serialize(format!("serde_json error: {}", e)))?;
             let url = {
                 if let Some(uuid) = &self.uuid {
                     format!("{}/api/v1/bundles?uuid={}", self.endpoint, uuid)
@@ -129,6 +123,7 @@ impl crate::platform_clients::SendTxEncoded for Jito {
                     format!("{}/api/v1/bundles", self.endpoint)
                 }
             };
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -136,38 +131,35 @@ impl crate::platform_clients::SendTxEncoded for Jito {
                 .body(request_body)
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("jito send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             log::info!("jito response: {:?}", response);
-            Ok(())
+            let bundle_id = parse_json_rpc_result(&response)?;
+            log::info!("jito bundle id: {}", bundle_id);
+            Ok(SubmitReceipt {
+                platform: PlatformName::Jito,
+                endpoint: format!("{}/api/v1/bundles", self.endpoint),
+                latency,
+                http_status: Some(status),
+                server_id: bundle_id.as_str().map(str::to_string),
+                raw: Some(response),
+            })
         })
     }
 }
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendBundle for Jito {
-    async fn send_bundle(&self, txs: &[SolTx]) -> Result<Vec<Signature>, String> {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
         log_time!("jito bundle send: ", {
             // 将所有交易序列化并 base64 编码
             let mut encoded_txs = Vec::with_capacity(txs.len());
-            let mut sigs: Vec<Signature> = Vec::with_capacity(txs.len());
             for tx in txs {
-                let encode_tx = match bincode::serialize(tx) {
-                    Ok(bytes) => base64::prelude::BASE64_STANDARD.encode(&bytes),
-                    Err(e) => return Err(format!("bincode serialize error: {}", e)),
-                };
-                encoded_txs.push(encode_tx);
-                sigs.push(tx.sig());
+                encoded_txs.push(tx.to_base64()?);
             }
-            let request_body = match serde_json::to_string(&json!({
+            let request_body = serde_json::to_string(&json!({
                 "id": 1,
                 "jsonrpc": "2.0",
                 "method": "sendBundle",
@@ -175,11 +167,10 @@ impl crate::platform_clients::SendBundle for Jito {
                     encoded_txs,
                     { "encoding": "base64" }
                 ]
-            })) {
-                Ok(body) => body,
-                Err(e) => return Err(format!("serde_json error: {}", e)),
-            };
+            }))
+            .map_err(|e| SendError::Serialize(format!("serde_json error: {}", e)))?;
             let url = format!("{}/api/v1/bundles", self.endpoint);
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -187,33 +178,37 @@ impl crate::platform_clients::SendBundle for Jito {
                 .body(request_body)
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("jito bundle send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             log::info!("jito raw response: {:?}", response);
             // 尝试用结构体解析响应
             match serde_json::from_str::<JitoSendBundleResponse>(&response) {
                 Ok(resp_obj) => {
                     if let Some(result) = resp_obj.result {
                         log::info!("jito bundle id: {}", result);
-                        Ok(sigs)
+                        Ok(SubmitReceipt {
+                            platform: PlatformName::Jito,
+                            endpoint: url,
+                            latency,
+                            http_status: Some(status),
+                            server_id: Some(result),
+                            raw: Some(response),
+                        })
                     } else if let Some(err) = resp_obj.error {
-                        Err(format!("jito error: {}", err))
+                        Err(SendError::from_rpc_error(&err))
                     } else {
-                        Err(format!("jito unknown response: {}", response))
+                        Err(SendError::Parse(format!(
+                            "jito unknown response: {}",
+                            response
+                        )))
                     }
                 }
-                Err(e) => Err(format!(
+                Err(e) => Err(SendError::Parse(format!(
                     "jito response parse error: {}, raw: {}",
                     e, response
-                )),
+                ))),
             }
         })
     }
@@ -231,9 +226,12 @@ impl crate::platform_clients::BuildTx for Jito {
     }
 
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        JITO_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Jito, JITO_TIP_ACCOUNTS)
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
 
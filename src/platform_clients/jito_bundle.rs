@@ -0,0 +1,462 @@
+//! Jito bundle 状态跟踪
+//!
+//! `getInflightBundleStatuses` 给出 Invalid / Pending / Failed / Landed，
+//! 落地后再用 `getBundleStatuses` 读取 slot 与确认级别。
+//! `Jito::send_bundle_tracked` 返回的 `BundleHandle` 可等待最终状态，或订阅每次变化。
+
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use serde_json::{Value, json};
+use solana_sdk::signature::Signature;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::jito::Jito;
+use crate::platform_clients::{SendBundle, SolTx, SubmitReceipt};
+
+/// `getInflightBundleStatuses` 返回的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflightStatus {
+    /// Jito 没有该 bundle（5 分钟回看窗口外或从未收到）
+    Invalid,
+    Pending,
+    /// 所有 region 都未能落地
+    Failed,
+    Landed,
+}
+
+/// `getInflightBundleStatuses` 的单条结果
+#[derive(Debug, Clone)]
+pub struct InflightBundleStatus {
+    pub bundle_id: String,
+    pub status: InflightStatus,
+    pub landed_slot: Option<u64>,
+}
+
+/// `getBundleStatuses` 的单条结果
+#[derive(Debug, Clone)]
+pub struct BundleStatus {
+    pub bundle_id: String,
+    pub transactions: Vec<Signature>,
+    pub slot: u64,
+    /// processed / confirmed / finalized
+    pub confirmation_status: Option<String>,
+    /// 执行错误，成功时为 `None`
+    pub err: Option<Value>,
+}
+
+/// 跟踪中的 bundle 状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleState {
+    /// 尚未查询到结果
+    Unknown,
+    Pending,
+    /// 曾处于 Pending 后变为 Invalid，视为被丢弃
+    Dropped,
+    /// 超过 `invalid_grace` 仍查不到且从未 Pending，视为 block engine 未接收
+    Invalid,
+    Failed,
+    Landed {
+        slot: u64,
+        confirmation_status: Option<String>,
+        err: Option<String>,
+    },
+    /// 超过跟踪时长仍无最终结果
+    TimedOut,
+}
+
+impl BundleState {
+    /// 不会再变化的状态；已落地但确认级别低于 confirmed 时仍继续跟踪
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            BundleState::Dropped
+            | BundleState::Invalid
+            | BundleState::Failed
+            | BundleState::TimedOut => true,
+            BundleState::Landed {
+                confirmation_status,
+                ..
+            } => matches!(
+                confirmation_status.as_deref(),
+                Some("confirmed") | Some("finalized")
+            ),
+            _ => false,
+        }
+    }
+}
+
+/// 跟踪参数
+#[derive(Debug, Clone, Copy)]
+pub struct BundleTrackConfig {
+    pub interval: Duration,
+    pub timeout: Duration,
+    /// 提交后多久仍为 Invalid 即判定未被接收；刚提交时可能短暂查不到
+    pub invalid_grace: Duration,
+}
+
+impl Default for BundleTrackConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            timeout: Duration::from_secs(60),
+            invalid_grace: Duration::from_secs(5),
+        }
+    }
+}
+
+/// 已提交 bundle 的跟踪句柄，drop 时停止轮询
+pub struct BundleHandle {
+    pub bundle_id: String,
+    rx: watch::Receiver<BundleState>,
+    task: JoinHandle<()>,
+}
+
+impl BundleHandle {
+    /// 当前状态
+    pub fn state(&self) -> BundleState {
+        self.rx.borrow().clone()
+    }
+
+    /// 订阅状态变化
+    pub fn updates(&self) -> watch::Receiver<BundleState> {
+        self.rx.clone()
+    }
+
+    /// 等待最终状态（`is_terminal`）
+    pub async fn wait(&self) -> BundleState {
+        let mut rx = self.rx.clone();
+        loop {
+            let state = rx.borrow_and_update().clone();
+            if state.is_terminal() {
+                return state;
+            }
+            if rx.changed().await.is_err() {
+                return rx.borrow().clone();
+            }
+        }
+    }
+}
+
+impl Drop for BundleHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Jito {
+    pub(crate) fn status_url(&self, method: &str) -> String {
+        match &self.uuid {
+            Some(uuid) if !uuid.is_empty() => {
+                format!("{}/api/v1/{}?uuid={}", self.endpoint, method, uuid)
+            }
+            _ => format!("{}/api/v1/{}", self.endpoint, method),
+        }
+    }
+
+    async fn bundle_rpc(&self, method: &str, bundle_ids: &[String]) -> Result<Value, SendError> {
+        let res = self
+            .http_client
+            .post(self.status_url(method))
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": [bundle_ids],
+            }))
+            .send()
+            .await;
+        let (_, body) = read_http_response(res).await?;
+        parse_json_rpc_result(&body)
+    }
+
+    /// 查询已落地 bundle 的 slot 与确认级别，未找到的位置为 `None`
+    pub async fn get_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<Option<BundleStatus>>, SendError> {
+        let result = self.bundle_rpc("getBundleStatuses", bundle_ids).await?;
+        parse_bundle_statuses(&result)
+    }
+
+    /// 查询最近 5 分钟内 bundle 的处理状态
+    pub async fn get_inflight_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<Option<InflightBundleStatus>>, SendError> {
+        let result = self
+            .bundle_rpc("getInflightBundleStatuses", bundle_ids)
+            .await?;
+        parse_inflight_bundle_statuses(&result)
+    }
+
+    /// 发送 bundle 并开始跟踪其状态
+    pub async fn send_bundle_tracked(
+        &self,
+        txs: &[SolTx],
+        config: BundleTrackConfig,
+    ) -> Result<(SubmitReceipt, BundleHandle), SendError> {
+        let receipt = self.send_bundle(txs).await?;
+        let bundle_id = receipt
+            .server_id
+            .clone()
+            .ok_or_else(|| SendError::Parse("jito sendBundle returned no bundle id".to_string()))?;
+        let handle = self.track_bundle(bundle_id, config);
+        Ok((receipt, handle))
+    }
+
+    /// 跟踪已提交的 bundle
+    pub fn track_bundle(&self, bundle_id: String, config: BundleTrackConfig) -> BundleHandle {
+        let (tx, rx) = watch::channel(BundleState::Unknown);
+        let jito = self.clone();
+        let id = bundle_id.clone();
+        let task = tokio::spawn(async move {
+            let started = Instant::now();
+            let ids = [id];
+            let mut seen_pending = false;
+            loop {
+                let invalid_final = seen_pending || started.elapsed() >= config.invalid_grace;
+                let state = match poll_bundle(&jito, &ids, seen_pending, invalid_final).await {
+                    Ok(state) => state,
+                    Err(e) => {
+                        warn!("[JitoBundle] poll {} failed: {}", ids[0], e);
+                        None
+                    }
+                };
+                if let Some(state) = state {
+                    seen_pending |= state == BundleState::Pending;
+                    if *tx.borrow() != state {
+                        info!("[JitoBundle] {} -> {:?}", ids[0], state);
+                        tx.send_replace(state.clone());
+                    }
+                    if state.is_terminal() {
+                        return;
+                    }
+                }
+                if started.elapsed() >= config.timeout {
+                    // 已落地但未到 confirmed 时保留 Landed，不覆盖为 TimedOut
+                    if !matches!(*tx.borrow(), BundleState::Landed { .. }) {
+                        tx.send_replace(BundleState::TimedOut);
+                    }
+                    return;
+                }
+                tokio::time::sleep(config.interval).await;
+            }
+        });
+        BundleHandle {
+            bundle_id,
+            rx,
+            task,
+        }
+    }
+}
+
+/// 轮询一次，没有新信息时返回 `None`。`invalid_final` 为 false 时忽略 Invalid
+async fn poll_bundle(
+    jito: &Jito,
+    ids: &[String],
+    seen_pending: bool,
+    invalid_final: bool,
+) -> Result<Option<BundleState>, SendError> {
+    let inflight = jito.get_inflight_bundle_statuses(ids).await?;
+    let Some(inflight) = inflight.into_iter().next().flatten() else {
+        return Ok(None);
+    };
+    let state = match inflight.status {
+        InflightStatus::Pending => BundleState::Pending,
+        InflightStatus::Failed => BundleState::Failed,
+        // 刚提交时可能短暂查不到，Pending 之后或超过宽限期的 Invalid 才是最终结果
+        InflightStatus::Invalid if seen_pending => BundleState::Dropped,
+        InflightStatus::Invalid if invalid_final => BundleState::Invalid,
+        InflightStatus::Invalid => return Ok(None),
+        InflightStatus::Landed => {
+            let landed = jito.get_bundle_statuses(ids).await?;
+            match landed.into_iter().next().flatten() {
+                Some(status) => BundleState::Landed {
+                    slot: status.slot,
+                    confirmation_status: status.confirmation_status,
+                    err: status.err.map(|e| e.to_string()),
+                },
+                None => BundleState::Landed {
+                    slot: inflight.landed_slot.unwrap_or_default(),
+                    confirmation_status: None,
+                    err: None,
+                },
+            }
+        }
+    };
+    Ok(Some(state))
+}
+
+/// 解析 `{"context": {...}, "value": [{"bundle_id": "..", "status": "Landed", "landed_slot": 1}]}`
+fn parse_inflight_bundle_statuses(
+    result: &Value,
+) -> Result<Vec<Option<InflightBundleStatus>>, SendError> {
+    let entries = result["value"]
+        .as_array()
+        .ok_or_else(|| SendError::Parse(format!("missing value, raw: {}", result)))?;
+    entries
+        .iter()
+        .map(|entry| {
+            if entry.is_null() {
+                return Ok(None);
+            }
+            let status = match entry["status"].as_str() {
+                Some("Invalid") => InflightStatus::Invalid,
+                Some("Pending") => InflightStatus::Pending,
+                Some("Failed") => InflightStatus::Failed,
+                Some("Landed") => InflightStatus::Landed,
+                _ => {
+                    return Err(SendError::Parse(format!(
+                        "unknown inflight status: {}",
+                        entry
+                    )));
+                }
+            };
+            Ok(Some(InflightBundleStatus {
+                bundle_id: entry["bundle_id"].as_str().unwrap_or_default().to_string(),
+                status,
+                landed_slot: entry["landed_slot"].as_u64(),
+            }))
+        })
+        .collect()
+}
+
+/// 解析 `{"context": {...}, "value": [{"bundle_id": "..", "transactions": [..], "slot": 1,
+/// "confirmation_status": "confirmed", "err": {"Ok": null}}]}`
+fn parse_bundle_statuses(result: &Value) -> Result<Vec<Option<BundleStatus>>, SendError> {
+    let entries = result["value"]
+        .as_array()
+        .ok_or_else(|| SendError::Parse(format!("missing value, raw: {}", result)))?;
+    entries
+        .iter()
+        .map(|entry| {
+            if entry.is_null() {
+                return Ok(None);
+            }
+            let slot = entry["slot"]
+                .as_u64()
+                .ok_or_else(|| SendError::Parse(format!("missing slot: {}", entry)))?;
+            let transactions = entry["transactions"]
+                .as_array()
+                .map(|sigs| {
+                    sigs.iter()
+                        .filter_map(|s| s.as_str()?.parse().ok())
+                        .collect()
+                })
+                .unwrap_or_default();
+            // 成功时为 {"Ok": null}
+            let err = match &entry["err"] {
+                Value::Null => None,
+                Value::Object(map) if map.get("Ok").is_some_and(Value::is_null) => None,
+                other => Some(other.clone()),
+            };
+            Ok(Some(BundleStatus {
+                bundle_id: entry["bundle_id"].as_str().unwrap_or_default().to_string(),
+                transactions,
+                slot,
+                confirmation_status: entry["confirmation_status"].as_str().map(str::to_string),
+                err,
+            }))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_jito_bundle_tracking() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use solana_sdk::hash::Hash;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+    use solana_system_interface::instruction::transfer;
+
+    use crate::platform_clients::Region;
+
+    let inflight_calls = Arc::new(AtomicU32::new(0));
+    let calls = Arc::clone(&inflight_calls);
+    let url = crate::test_utils::mock_json_rpc(move |req| {
+        let id = req["params"][0][0].as_str().unwrap_or_default().to_string();
+        match req["method"].as_str() {
+            Some("getInflightBundleStatuses") => {
+                let status = match id.as_str() {
+                    // Invalid → Pending → Landed
+                    "b1" => match calls.fetch_add(1, Ordering::SeqCst) {
+                        0 => "Invalid",
+                        1 => "Pending",
+                        _ => "Landed",
+                    },
+                    // 从未被接收
+                    "b2" => "Invalid",
+                    _ => "Landed",
+                };
+                json!({
+                    "context": { "slot": 100 },
+                    "value": [{ "bundle_id": id, "status": status, "landed_slot": 101 }]
+                })
+            }
+            Some("getBundleStatuses") => json!({
+                "context": { "slot": 102 },
+                "value": [{
+                    "bundle_id": id,
+                    "transactions": [Signature::default().to_string()],
+                    "slot": 101,
+                    // b3 停在 processed
+                    "confirmation_status": if id == "b3" { "processed" } else { "confirmed" },
+                    "err": { "Ok": null }
+                }]
+            }),
+            _ => json!("b1"),
+        }
+    })
+    .await;
+
+    let mut jito = Jito::init_with("", Region::Frankfurt);
+    jito.endpoint = url;
+    let payer = Keypair::new();
+    let txs = vec![SolTx::Legacy(Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    ))];
+    let config = BundleTrackConfig {
+        interval: Duration::from_millis(10),
+        timeout: Duration::from_secs(5),
+        invalid_grace: Duration::from_secs(5),
+    };
+    let (receipt, handle) = jito.send_bundle_tracked(&txs, config).await.unwrap();
+    assert_eq!(receipt.server_id.as_deref(), Some("b1"));
+    assert_eq!(
+        handle.wait().await,
+        BundleState::Landed {
+            slot: 101,
+            confirmation_status: Some("confirmed".to_string()),
+            err: None,
+        }
+    );
+    assert!(inflight_calls.load(Ordering::SeqCst) >= 3);
+
+    // 超过宽限期仍为 Invalid
+    let config = BundleTrackConfig {
+        invalid_grace: Duration::from_millis(30),
+        ..config
+    };
+    let handle = jito.track_bundle("b2".to_string(), config);
+    assert_eq!(handle.wait().await, BundleState::Invalid);
+
+    // 超时时已落地的状态不被 TimedOut 覆盖
+    let config = BundleTrackConfig {
+        timeout: Duration::from_millis(50),
+        ..config
+    };
+    let handle = jito.track_bundle("b3".to_string(), config);
+    assert!(matches!(
+        handle.wait().await,
+        BundleState::Landed { slot: 101, .. }
+    ));
+}
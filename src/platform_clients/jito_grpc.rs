@@ -0,0 +1,348 @@
+//! Jito Block Engine gRPC searcher 客户端
+//!
+//! 与 `Jito`（JSON-RPC over HTTP）使用同一组 block engine 域名，
+//! 认证走与 Harmonic 相同的 challenge-response 流程，`Role` 为 `JitoSearcher`（1）。
+//! 单笔交易也以一笔 bundle 的形式发送，因此最低 tip 按 bundle 计。
+
+use crate::error::SendError;
+use crate::platform_clients::block_engine::{
+    BlockEngineSession, bundle_of, check_bundle, classify_error, with_bearer,
+};
+use crate::platform_clients::harmonic_proto::{
+    auth::Role,
+    bundle::{Bundle, BundleResult},
+    searcher::{
+        ConnectedLeadersRequest, ConnectedLeadersResponse, GetTipAccountsRequest,
+        NextScheduledLeaderRequest, NextScheduledLeaderResponse, SendBundleRequest,
+        SubscribeBundleResultsRequest,
+    },
+};
+use crate::platform_clients::jito::{JITO_TIP_ACCOUNTS, Jito};
+use crate::platform_clients::{PlatformName, Region, SolTx, SubmitReceipt};
+use crate::tip_accounts::{self, TipAccountSource};
+
+use anyhow::Context;
+use base64::Engine;
+use log::{error, info};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+use tonic::{Request, Streaming};
+
+#[derive(Clone)]
+pub struct JitoGrpc {
+    /// 长连接与 token 缓存，clone 之间共享
+    session: Arc<BlockEngineSession>,
+}
+
+impl fmt::Display for JitoGrpc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "JitoGrpc")
+    }
+}
+
+impl JitoGrpc {
+    pub fn new(searcher: Arc<Keypair>) -> Self {
+        Self::init_with(searcher, *crate::constants::REGION)
+    }
+
+    /// 显式构造：调用方负责提供 searcher keypair 和 region，不读取任何环境变量。
+    pub fn init_with(searcher: Arc<Keypair>, region: Region) -> Self {
+        Self::init_with_endpoint(searcher, Jito::endpoint_for(region))
+    }
+
+    /// 指定 endpoint（用于测试或自建转发）。
+    pub fn init_with_endpoint(searcher: Arc<Keypair>, endpoint: impl Into<String>) -> Self {
+        Self {
+            session: Arc::new(BlockEngineSession::new(
+                endpoint.into(),
+                searcher,
+                Role::JitoSearcher,
+            )),
+        }
+    }
+
+    pub fn endpoint(&self) -> &str {
+        self.session.endpoint()
+    }
+
+    /// 提前建立连接并完成认证，避免首笔发送承担握手开销。
+    pub async fn warm_up(&self) -> Result<(), SendError> {
+        self.session
+            .access_token()
+            .await
+            .map(|_| ())
+            .map_err(|e| classify_error(&e))
+    }
+
+    /// 按顺序发送一组序列化后的交易，回执的 `server_id` 为 bundle uuid。
+    pub async fn send_bundle_bytes(&self, txs: Vec<Vec<u8>>) -> Result<SubmitReceipt, SendError> {
+        check_bundle(&txs)?;
+        let started = Instant::now();
+        let result: anyhow::Result<String> = async {
+            let bundle = bundle_of(txs)?;
+            self.session
+                .call(|mut client, token| {
+                    let bundle = bundle.clone();
+                    async move {
+                        let resp = client
+                            .send_bundle(send_bundle_request(bundle, &token)?)
+                            .await
+                            .context("SendBundle gRPC failed")?
+                            .into_inner();
+                        Ok(resp.uuid)
+                    }
+                })
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(uuid) => {
+                info!("[JitoGrpc] {} → uuid={}", self.endpoint(), uuid);
+                Ok(SubmitReceipt {
+                    platform: PlatformName::Jito,
+                    endpoint: self.endpoint().to_string(),
+                    latency: started.elapsed(),
+                    http_status: None,
+                    raw: Some(uuid.clone()),
+                    server_id: Some(uuid),
+                })
+            }
+            Err(e) => {
+                error!("[JitoGrpc] {} failed: {:#}", self.endpoint(), e);
+                Err(classify_error(&e))
+            }
+        }
+    }
+
+    /// `GetTipAccounts`
+    pub async fn get_tip_accounts(&self) -> Result<Vec<Pubkey>, SendError> {
+        let accounts = self
+            .session
+            .call(|mut client, token| async move {
+                let resp = client
+                    .get_tip_accounts(with_bearer(GetTipAccountsRequest {}, &token)?)
+                    .await
+                    .context("GetTipAccounts gRPC failed")?
+                    .into_inner();
+                Ok(resp.accounts)
+            })
+            .await
+            .map_err(|e| classify_error(&e))?;
+        parse_tip_accounts(&accounts)
+    }
+
+    /// `GetNextScheduledLeader`，`regions` 为空时不限区域。
+    pub async fn get_next_scheduled_leader(
+        &self,
+        regions: Vec<String>,
+    ) -> Result<NextScheduledLeaderResponse, SendError> {
+        self.session
+            .call(|mut client, token| {
+                let regions = regions.clone();
+                async move {
+                    let resp = client
+                        .get_next_scheduled_leader(with_bearer(
+                            NextScheduledLeaderRequest { regions },
+                            &token,
+                        )?)
+                        .await
+                        .context("GetNextScheduledLeader gRPC failed")?
+                        .into_inner();
+                    Ok(resp)
+                }
+            })
+            .await
+            .map_err(|e| classify_error(&e))
+    }
+
+    /// `GetConnectedLeaders`：validator identity → 其 leader slot 列表。
+    pub async fn get_connected_leaders(&self) -> Result<HashMap<String, Vec<u64>>, SendError> {
+        self.session
+            .call(|mut client, token| async move {
+                let resp = client
+                    .get_connected_leaders(with_bearer(ConnectedLeadersRequest {}, &token)?)
+                    .await
+                    .context("GetConnectedLeaders gRPC failed")?
+                    .into_inner();
+                Ok(connected_leaders(resp))
+            })
+            .await
+            .map_err(|e| classify_error(&e))
+    }
+
+    /// `SubscribeBundleResults`：返回本 searcher 所发 bundle 的结果流。
+    pub async fn subscribe_bundle_results(&self) -> Result<Streaming<BundleResult>, SendError> {
+        self.session
+            .call(|mut client, token| async move {
+                let stream = client
+                    .subscribe_bundle_results(with_bearer(
+                        SubscribeBundleResultsRequest {},
+                        &token,
+                    )?)
+                    .await
+                    .context("SubscribeBundleResults gRPC failed")?
+                    .into_inner();
+                Ok(stream)
+            })
+            .await
+            .map_err(|e| classify_error(&e))
+    }
+}
+
+fn send_bundle_request(bundle: Bundle, token: &str) -> anyhow::Result<Request<SendBundleRequest>> {
+    with_bearer(
+        SendBundleRequest {
+            bundle: Some(bundle),
+        },
+        token,
+    )
+}
+
+fn parse_tip_accounts(accounts: &[String]) -> Result<Vec<Pubkey>, SendError> {
+    accounts
+        .iter()
+        .map(|account| {
+            account
+                .parse()
+                .map_err(|_| SendError::Parse(format!("invalid tip account: {}", account)))
+        })
+        .collect()
+}
+
+fn connected_leaders(resp: ConnectedLeadersResponse) -> HashMap<String, Vec<u64>> {
+    resp.connected_validators
+        .into_iter()
+        .map(|(identity, slots)| (identity, slots.slots))
+        .collect()
+}
+
+// ── trait 实现 ────────────────────────────────────────────────────────────────
+
+#[async_trait::async_trait]
+impl crate::platform_clients::SendTxEncoded for JitoGrpc {
+    /// 接收 base64 编码的交易，解码后作为单笔 bundle 发送。
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+        let tx_bytes = base64::prelude::BASE64_STANDARD
+            .decode(tx_base64)
+            .map_err(|e| SendError::Serialize(format!("base64 decode failed: {}", e)))?;
+        self.send_bundle_bytes(vec![tx_bytes]).await
+    }
+
+    /// 序列化后的交易字节直接装入 Packet，不再做 base64 往返。
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
+        self.send_bundle_bytes(vec![tx_bytes.to_vec()]).await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::platform_clients::SendBundle for JitoGrpc {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        let mut tx_bytes = Vec::with_capacity(txs.len());
+        for tx in txs {
+            tx_bytes.push(tx.to_bytes()?);
+        }
+        self.send_bundle_bytes(tx_bytes).await
+    }
+}
+
+impl crate::platform_clients::BuildTx for JitoGrpc {
+    fn get_tip_address(&self) -> Pubkey {
+        tip_accounts::tip_address(PlatformName::Jito, JITO_TIP_ACCOUNTS)
+    }
+    fn get_min_tip_amount(&self) -> u64 {
+        Jito::MIN_TIP_AMOUNT_BUNDLE
+    }
+    fn platform(&self) -> PlatformName {
+        PlatformName::Jito
+    }
+    fn tip_recvs(&self) -> Vec<Pubkey> {
+        tip_accounts::tip_recvs(PlatformName::Jito, JITO_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Jito::DEFAULT_TPS)
+    }
+}
+
+impl crate::platform_clients::BuildBundle for JitoGrpc {
+    fn build_bundle<'a>(
+        &'a self,
+        txs: &[SolTx],
+    ) -> crate::platform_clients::BundleEnvelope<'a, JitoGrpc> {
+        crate::platform_clients::BundleEnvelope {
+            txs: txs.to_vec(),
+            sender: self,
+        }
+    }
+}
+
+/// gRPC `GetTipAccounts`，可直接交给 `TipAccountProvider`
+#[async_trait::async_trait]
+impl TipAccountSource for JitoGrpc {
+    async fn fetch_tip_accounts(&self) -> Result<Vec<Pubkey>, SendError> {
+        self.get_tip_accounts().await
+    }
+}
+
+#[tokio::test]
+async fn test_jito_grpc_request() {
+    use crate::constants::{MAX_BUNDLE_TXS, PACKET_DATA_SIZE};
+    use crate::platform_clients::block_engine::bundle_of;
+
+    let searcher = Arc::new(Keypair::new());
+    let client = JitoGrpc::init_with(searcher.clone(), Region::Tokyo);
+    assert_eq!(client.endpoint(), Jito::endpoint_for(Region::Tokyo));
+    let client = JitoGrpc::init_with_endpoint(searcher, "http://127.0.0.1:1");
+    assert_eq!(client.endpoint(), "http://127.0.0.1:1");
+
+    // bundle 按顺序装入，带 bearer token
+    let req = send_bundle_request(bundle_of(vec![vec![1; 3], vec![2; 5]]).unwrap(), "t").unwrap();
+    assert_eq!(req.metadata().get("authorization").unwrap(), "Bearer t");
+    let packets = &req.get_ref().bundle.as_ref().unwrap().packets;
+    assert_eq!(packets.len(), 2);
+    assert_eq!(packets[1].data, vec![2; 5]);
+
+    // 交易数或大小不合法时不建立连接
+    assert_eq!(
+        client.send_bundle_bytes(vec![]).await.unwrap_err(),
+        SendError::BundleSize {
+            count: 0,
+            min: 1,
+            max: MAX_BUNDLE_TXS
+        }
+    );
+    assert!(matches!(
+        client
+            .send_bundle_bytes(vec![vec![0; PACKET_DATA_SIZE + 1]])
+            .await,
+        Err(SendError::Oversize { .. })
+    ));
+}
+
+#[test]
+fn test_jito_grpc_response() {
+    use crate::platform_clients::harmonic_proto::searcher::SlotList;
+
+    let tip = JITO_TIP_ACCOUNTS[0];
+    assert_eq!(parse_tip_accounts(&[tip.to_string()]), Ok(vec![tip]));
+    assert!(matches!(
+        parse_tip_accounts(&[tip.to_string(), "bad".to_string()]),
+        Err(SendError::Parse(_))
+    ));
+
+    let resp = ConnectedLeadersResponse {
+        connected_validators: HashMap::from([(
+            "validator".to_string(),
+            SlotList {
+                slots: vec![100, 101],
+            },
+        )]),
+    };
+    assert_eq!(
+        connected_leaders(resp),
+        HashMap::from([("validator".to_string(), vec![100, 101])])
+    );
+}
@@ -1,4 +1,6 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_sdk::account::Account;
 use solana_sdk::message::AddressLookupTableAccount;
 use solana_sdk::transaction::Transaction;
 
@@ -7,11 +9,14 @@ use solana_system_interface::instruction::advance_nonce_account;
 use solana_system_interface::instruction::transfer;
 use std::fmt::Display;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use utils::log_time;
 
-use crate::constants::HTTP_CLIENT;
+use crate::constants::{HTTP_CLIENT, MAX_BUNDLE_TXS, PACKET_DATA_SIZE};
+use crate::error::{BuildError, NonceError, SendError, parse_json_rpc_result, read_http_response};
+use crate::tip::TipStrategy;
+use base64::Engine;
 use log::info;
 use solana_sdk::hash::Hash;
 use solana_sdk::instruction::Instruction;
@@ -20,14 +25,18 @@ use solana_sdk::signature::{Keypair, Signature};
 use solana_sdk::signer::Signer;
 pub mod astralane;
 pub mod astralane_quic;
+mod block_engine;
 pub mod blockrazor;
 pub mod ever_stake;
 pub mod ever_stake_quic;
 pub mod flash_block;
 pub mod harmonic;
+pub mod harmonic_bundle;
 pub mod harmonic_proto;
 pub mod helius;
 pub mod jito;
+pub mod jito_bundle;
+pub mod jito_grpc;
 pub mod nextblock;
 pub mod nodeone;
 pub mod stellium;
@@ -43,18 +52,25 @@ pub enum SolTx {
 }
 
 impl SolTx {
-    /// 将交易序列化为 base64 字符串
-    pub fn to_base64(&self) -> Result<String, Box<dyn std::error::Error>> {
-        match self {
-            SolTx::Legacy(tx) => {
-                let data = bincode::serialize(tx)?;
-                Ok(base64::encode(data))
-            }
-            SolTx::V0(v0tx) => {
-                let data = bincode::serialize(v0tx)?;
-                Ok(base64::encode(data))
-            }
+    /// 将交易序列化为 wire 字节，超过 `PACKET_DATA_SIZE` 时返回 `Oversize`
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SendError> {
+        let data = match self {
+            SolTx::Legacy(tx) => bincode::serialize(tx),
+            SolTx::V0(v0tx) => bincode::serialize(v0tx),
+        }
+        .map_err(|e| SendError::Serialize(e.to_string()))?;
+        if data.len() > PACKET_DATA_SIZE {
+            return Err(SendError::Oversize {
+                size: data.len(),
+                max: PACKET_DATA_SIZE,
+            });
         }
+        Ok(data)
+    }
+
+    /// 将交易序列化为 base64 字符串
+    pub fn to_base64(&self) -> Result<String, SendError> {
+        Ok(base64::prelude::BASE64_STANDARD.encode(self.to_bytes()?))
     }
     pub fn sig(&self) -> Signature {
         match self {
@@ -62,6 +78,37 @@ impl SolTx {
             SolTx::V0(versioned_transaction) => versioned_transaction.signatures[0],
         }
     }
+
+    /// 交易的 recent blockhash，durable nonce 交易即为 nonce 值
+    pub fn recent_blockhash(&self) -> Hash {
+        match self {
+            SolTx::Legacy(tx) => tx.message.recent_blockhash,
+            SolTx::V0(tx) => *tx.message.recent_blockhash(),
+        }
+    }
+
+    /// 第一条指令是 advance-nonce 时返回 `(nonce account, authority)`。
+    ///
+    /// 只解析 message 的静态账户，nonce 账户经 lookup table 加载时返回 `None`。
+    pub fn advance_nonce(&self) -> Option<(Pubkey, Pubkey)> {
+        let (keys, ixs) = match self {
+            SolTx::Legacy(tx) => (&tx.message.account_keys[..], &tx.message.instructions[..]),
+            SolTx::V0(tx) => (tx.message.static_account_keys(), tx.message.instructions()),
+        };
+        let ix = ixs.first()?;
+        let key = |idx: &u8| keys.get(*idx as usize).copied();
+        let program_id = key(&ix.program_id_index)?;
+        let accounts = ix.accounts.iter().map(key).collect::<Option<Vec<_>>>()?;
+        let (account, authority) = (*accounts.first()?, *accounts.get(2)?);
+
+        let expected = advance_nonce_account(&account, &authority);
+        let matches = program_id == expected.program_id
+            && ix.data == expected.data
+            && accounts
+                .iter()
+                .eq(expected.accounts.iter().map(|meta| &meta.pubkey));
+        matches.then_some((account, authority))
+    }
 }
 
 // 自定义 SolTx 的 Serialize 实现，只序列化内部内容，不包含变体信息
@@ -77,7 +124,7 @@ impl serde::Serialize for SolTx {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// 支持的平台类型
 pub enum PlatformName {
     Astralane,
@@ -120,6 +167,7 @@ impl std::fmt::Display for PlatformName {
 pub struct DetailedTx {
     pub tx: SolTx,
     pub platform: PlatformName,
+    /// 实际写入交易的 tip（lamports），没有 tip 指令时为 `None`
     pub tip: Option<u64>,
     pub cu_limit: Option<u32>,
     pub cu_price: Option<u64>,
@@ -144,20 +192,144 @@ impl HashParam {
             HashParam::NonceAccount { hash, .. } => hash,
         }
     }
+
+    /// 从链上读取 nonce 账户，校验 authority 后填入当前 nonce 值
+    pub async fn from_nonce_account(
+        rpc: &RpcClient,
+        account: &Pubkey,
+        authority: &Pubkey,
+    ) -> Result<Self, NonceError> {
+        let (actual, hash) = crate::nonce::fetch_nonce_account(rpc, account).await?;
+        Self::checked_nonce(account, authority, actual, hash)
+    }
+
+    /// 用已读取的账户数据构造，校验规则同 `from_nonce_account`
+    pub fn from_nonce_account_data(
+        account: &Pubkey,
+        data: &Account,
+        authority: &Pubkey,
+    ) -> Result<Self, NonceError> {
+        let (actual, hash) = crate::nonce::parse_nonce_account(account, data)?;
+        Self::checked_nonce(account, authority, actual, hash)
+    }
+
+    fn checked_nonce(
+        account: &Pubkey,
+        authority: &Pubkey,
+        actual: Pubkey,
+        hash: Hash,
+    ) -> Result<Self, NonceError> {
+        if actual != *authority {
+            return Err(NonceError::AuthorityMismatch {
+                account: *account,
+                expected: *authority,
+                actual,
+            });
+        }
+        Ok(HashParam::NonceAccount {
+            account: *account,
+            authority: *authority,
+            hash,
+        })
+    }
+}
+/// 平台提交回执，记录平台对本次提交的确认信息，便于与链上结果关联
+#[derive(Debug, Clone)]
+pub struct SubmitReceipt {
+    pub platform: PlatformName,
+    /// 实际请求的 endpoint（不含 URL 中的 API key）
+    pub endpoint: String,
+    /// 请求耗时（发出请求到读完响应）
+    pub latency: Duration,
+    /// HTTP 状态码，QUIC / gRPC 传输为 `None`
+    pub http_status: Option<u16>,
+    /// 平台分配的 id：bundle id、uuid 或签名
+    pub server_id: Option<String>,
+    /// 原始响应 body
+    pub raw: Option<String>,
 }
+
 // 单笔交易发送 trait
 /// 单笔交易发送 trait，发送 base64 编码后的交易
+///
+/// `TxEnvelope` 走 `send_tx_raw`：JSON 平台沿用默认实现（编码为 base64 后转发），
+/// QUIC / gRPC 等二进制传输应覆盖 `send_tx_raw`，直接发送序列化后的 wire bytes。
 #[async_trait::async_trait]
 pub trait SendTxEncoded: Sync + Send {
     /// 发送 base64 编码后的交易
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String>;
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError>;
+
+    /// 发送 bincode 序列化后的交易字节
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
+        let b64 = base64::prelude::BASE64_STANDARD.encode(tx_bytes);
+        self.send_tx_encoded(&b64).await
+    }
 }
 
 // 批量交易发送 trait
-/// 批量交易发送 trait
+/// 批量交易发送 trait，签名可通过 `BundleEnvelope::sigs` 获取
 #[async_trait::async_trait]
 pub trait SendBundle: Sync + Send {
-    async fn send_bundle(&self, txs: &[SolTx]) -> Result<Vec<Signature>, String>;
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError>;
+}
+
+/// JSON-RPC `sendBundle` 公共实现（NodeOne / Temporal / ZeroSlot 共用）
+///
+/// `request` 为已带上 URL 与鉴权 header 的 POST 请求；`endpoint` 写入回执，不含 API key。
+/// 交易数不在 `1..=MAX_BUNDLE_TXS` 时不发请求，直接返回 `BundleSize`。
+pub(crate) async fn send_json_rpc_bundle(
+    platform: PlatformName,
+    request: reqwest::RequestBuilder,
+    endpoint: &str,
+    txs: &[SolTx],
+) -> Result<SubmitReceipt, SendError> {
+    if txs.is_empty() || txs.len() > MAX_BUNDLE_TXS {
+        return Err(SendError::BundleSize {
+            count: txs.len(),
+            min: 1,
+            max: MAX_BUNDLE_TXS,
+        });
+    }
+    let mut encoded_txs = Vec::with_capacity(txs.len());
+    for tx in txs {
+        encoded_txs.push(tx.to_base64()?);
+    }
+    let started = Instant::now();
+    let res = request
+        .header("Content-Type", "application/json")
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [
+                encoded_txs,
+                { "encoding": "base64" }
+            ],
+        }))
+        .send()
+        .await;
+    let (status, response) = read_http_response(res).await.inspect_err(|e| {
+        log::error!("{} bundle send error: {}", platform, e);
+    })?;
+    let latency = started.elapsed();
+    info!("{} bundle: {}", platform, response);
+    let bundle_id = parse_json_rpc_result(&response)?
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| {
+            SendError::Parse(format!(
+                "{} sendBundle returned no bundle id: {}",
+                platform, response
+            ))
+        })?;
+    Ok(SubmitReceipt {
+        platform,
+        endpoint: endpoint.to_string(),
+        latency,
+        http_status: Some(status),
+        server_id: Some(bundle_id),
+        raw: Some(response),
+    })
 }
 
 // 单笔交易组装 trait
@@ -176,13 +348,26 @@ pub trait BuildTx {
     fn uses_tip_transfer(&self) -> bool {
         true
     }
+
+    /// 平台默认允许的 TPS（即各客户端的 `DEFAULT_TPS`），`None` 表示不限速
+    fn default_tps(&self) -> Option<u64> {
+        None
+    }
+
+    /// 按 tip 策略计算实际写入的 tip（lamports），`uses_tip_transfer()` 为 `false` 时恒为 0
+    fn resolve_tip(&self, tip: &TipStrategy) -> u64 {
+        if !self.uses_tip_transfer() {
+            return 0;
+        }
+        tip.resolve(self.platform(), self.get_min_tip_amount())
+    }
     // 默认实现
     /// 默认交易组装实现，支持 tip、cu、nonce 等参数
     fn build_tx<'a>(
         &'a self,
         ixs: &[Instruction],
         signer: &Arc<Keypair>,
-        tip: &Option<u64>,
+        tip: &TipStrategy,
         nonce: &HashParam,
         cu: &(Option<u32>, Option<u64>),
         memo: Option<Vec<&str>>,
@@ -210,29 +395,20 @@ pub trait BuildTx {
                 instructions.push(ComputeBudgetInstruction::set_compute_unit_price(cu_price));
             }
 
-            // tip 转账指令：三重保护
-            // 1. uses_tip_transfer()=false 的平台（如 Harmonic）直接跳过
-            // 2. tip=Some(0) 跳过
-            // 3. tip_amt=0（如平台 min=0）跳过
-            if self.uses_tip_transfer() {
-                if let Some(0) = tip {
-                    // tip = Some(0) → 不添加 tip 指令
-                } else {
-                    let tip_address = self.get_tip_address();
-                    let tip_amt = tip.unwrap_or(self.get_min_tip_amount());
-                    if tip_amt > 0 {
-                        info!(
-                            "Build Tx with tip: {}({tip_amt}lamports) at {} tip address: {}",
-                            tip_amt as f64 / 1_000_000_000.0,
-                            self,
-                            tip_address
-                        );
-                        let tip_ix = transfer(&signer.pubkey(), &tip_address, tip_amt);
-                        instructions.push(tip_ix);
-                    }
-                }
+            // tip 转账指令：uses_tip_transfer()=false 的平台（如 Harmonic）、
+            // TipStrategy::Fixed(0)、平台 min=0 时 tip_amt 为 0，不写 tip 指令
+            let tip_amt = self.resolve_tip(tip);
+            if tip_amt > 0 {
+                let tip_address = self.get_tip_address();
+                info!(
+                    "Build Tx with tip: {}({tip_amt}lamports) at {} tip address: {}",
+                    tip_amt as f64 / 1_000_000_000.0,
+                    self,
+                    tip_address
+                );
+                let tip_ix = transfer(&signer.pubkey(), &tip_address, tip_amt);
+                instructions.push(tip_ix);
             }
-            // uses_tip_transfer()=false 时（如 Harmonic）直接跳过，不写 tip 指令
 
             if let Some(memo_list) = memo {
                 let memo_concat = memo_list.join("-");
@@ -258,7 +434,7 @@ pub trait BuildTx {
                 tx: DetailedTx {
                     tx: SolTx::Legacy(tx),
                     platform: self.platform(),
-                    tip: *tip,
+                    tip: (tip_amt > 0).then_some(tip_amt),
                     cu_limit: cu.0,
                     cu_price: cu.1,
                 },
@@ -278,12 +454,12 @@ pub trait BuildBundle {
 
 // 单笔 envelope
 /// 单笔交易 envelope，兼容 Legacy/V0，包含 SolTx 和发送者
-pub struct TxEnvelope<'a, T: SendTxEncoded + Sync + Send + 'a> {
+pub struct TxEnvelope<'a, T: SendTxEncoded + Sync + Send + ?Sized + 'a> {
     pub tx: DetailedTx,
     pub sender: &'a T,
 }
 
-impl<'a, T: SendTxEncoded + Sync + Send + 'a> TxEnvelope<'a, T> {
+impl<'a, T: SendTxEncoded + Sync + Send + ?Sized + 'a> TxEnvelope<'a, T> {
     /// 获取内部 SolTx
     pub fn inner_tx(&self) -> &SolTx {
         &self.tx.tx
@@ -294,20 +470,43 @@ impl<'a, T: SendTxEncoded + Sync + Send + 'a> TxEnvelope<'a, T> {
     }
 }
 
+/// 单笔交易的提交结果：签名在组装时即已确定，无论平台是否接收都可用于链上追踪
+#[derive(Debug, Clone)]
+pub struct SubmitOutcome {
+    pub sig: Signature,
+    pub result: Result<SubmitReceipt, SendError>,
+}
+
+impl SubmitOutcome {
+    /// 平台是否接收了本次提交
+    pub fn is_accepted(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
 /// 单笔交易发送 trait，异步发送并返回签名
 #[async_trait::async_trait]
 pub trait TxSend: Send + Sync {
-    async fn send(&self) -> Result<Signature, String>;
+    /// 提交交易，平台拒绝 / 超时 / 传输失败时返回对应的 `SendError`
+    async fn send(&self) -> Result<Signature, SendError> {
+        let outcome = self.submit().await;
+        outcome.result.map(|_| outcome.sig)
+    }
+    /// 提交交易，同时返回签名与平台回执（或错误）
+    async fn submit(&self) -> SubmitOutcome;
     fn sig(&self) -> Signature;
 }
 
 /// TxEnvelope 的发送实现，兼容 Legacy/V0
 #[async_trait::async_trait]
-impl<'a, T: SendTxEncoded + Sync + Send + 'a> TxSend for TxEnvelope<'a, T> {
-    async fn send(&self) -> Result<Signature, String> {
-        let b64 = self.inner_tx().to_base64().map_err(|e| e.to_string())?;
-        let _ = self.sender.send_tx_encoded(&b64).await;
-        Ok(self.inner_tx().sig())
+impl<'a, T: SendTxEncoded + Sync + Send + ?Sized + 'a> TxSend for TxEnvelope<'a, T> {
+    async fn submit(&self) -> SubmitOutcome {
+        let sig = self.inner_tx().sig();
+        let result = match self.inner_tx().to_bytes() {
+            Ok(bytes) => self.sender.send_tx_raw(&bytes).await,
+            Err(e) => Err(e),
+        };
+        SubmitOutcome { sig, result }
     }
     fn sig(&self) -> Signature {
         self.inner_tx().sig()
@@ -331,13 +530,13 @@ impl<'a, T: SendBundle + Sync + Send + 'a> BundleEnvelope<'a, T> {
 /// 批量交易发送 trait
 #[async_trait::async_trait]
 pub trait BundleSend {
-    async fn send_bundle(&self) -> Result<Vec<Signature>, String>;
+    async fn send_bundle(&self) -> Result<SubmitReceipt, SendError>;
 }
 
 /// BundleEnvelope 的批量发送实现
 #[async_trait::async_trait]
 impl<'a, T: SendBundle + Sync + Send + 'a> BundleSend for BundleEnvelope<'a, T> {
-    async fn send_bundle(&self) -> Result<Vec<Signature>, String> {
+    async fn send_bundle(&self) -> Result<SubmitReceipt, SendError> {
         self.sender.send_bundle(&self.txs).await
     }
 }
@@ -423,12 +622,12 @@ pub trait BuildV0Tx {
         &'a self,
         ixs: &[Instruction],
         signer: &Arc<Keypair>,
-        tip: &Option<u64>,
+        tip: &TipStrategy,
         nonce: &HashParam,
         cu: &(Option<u32>, Option<u64>),
         address_lookup_tables: &[AddressLookupTableAccount],
         memo: Option<Vec<&str>>,
-    ) -> Result<TxEnvelope<'a, Self>, Box<dyn std::error::Error + Send + Sync>>
+    ) -> Result<TxEnvelope<'a, Self>, BuildError>
     where
         Self: Sync + Send + Sized + Display + SendTxEncoded + BuildTx,
     {
@@ -458,25 +657,19 @@ pub trait BuildV0Tx {
                 instructions.push(price_instruction);
             }
 
-            if self.uses_tip_transfer() {
-                if let Some(0) = tip {
-                    // tip = Some(0) → 不添加 tip 指令
-                } else {
-                    let tip_address = self.get_tip_address();
-                    let tip_amt = tip.unwrap_or(self.get_min_tip_amount());
-                    if tip_amt > 0 {
-                        info!(
-                            "Build V0Tx with tip: {}({tip_amt}lamports) at {} tip address: {}",
-                            tip_amt as f64 / 1_000_000_000.0,
-                            self,
-                            tip_address
-                        );
-                        let tip_ix = transfer(&payer, &tip_address, tip_amt);
-                        instructions.push(tip_ix);
-                    }
-                }
+            // uses_tip_transfer() = false 时（如 Harmonic）tip_amt 为 0，不写 tip 指令
+            let tip_amt = self.resolve_tip(tip);
+            if tip_amt > 0 {
+                let tip_address = self.get_tip_address();
+                info!(
+                    "Build V0Tx with tip: {}({tip_amt}lamports) at {} tip address: {}",
+                    tip_amt as f64 / 1_000_000_000.0,
+                    self,
+                    tip_address
+                );
+                let tip_ix = transfer(&payer, &tip_address, tip_amt);
+                instructions.push(tip_ix);
             }
-            // uses_tip_transfer() = false 时（如 Harmonic）直接跳过，不写 tip 指令
             if let Some(memo_str) = memo {
                 let memo_concat = memo_str.join("-");
                 let memo_ix = solana_sdk::instruction::Instruction {
@@ -491,18 +684,20 @@ pub trait BuildV0Tx {
             instructions.extend(ixs.iter().cloned());
 
             let message =
-                V0Message::try_compile(&payer, &instructions, address_lookup_tables, hash)?;
+                V0Message::try_compile(&payer, &instructions, address_lookup_tables, hash)
+                    .map_err(|e| BuildError::Compile(e.to_string()))?;
             let transaction = VersionedTransaction::try_new(
                 solana_sdk::message::VersionedMessage::V0(message),
                 &[signer.as_ref()],
-            )?;
+            )
+            .map_err(|e| BuildError::Sign(e.to_string()))?;
             let sig = transaction.signatures[0];
             info!("  sig: {}", sig);
             Ok(TxEnvelope {
                 tx: DetailedTx {
                     tx: SolTx::V0(transaction),
                     platform: self.platform(),
-                    tip: *tip,
+                    tip: (tip_amt > 0).then_some(tip_amt),
                     cu_limit: cu.0,
                     cu_price: cu.1,
                 },
@@ -516,12 +711,12 @@ pub trait BuildV0Tx {
         &'a self,
         ixs: &[Instruction],
         signers: &[&Keypair],
-        tip: &Option<u64>,
+        tip: &TipStrategy,
         nonce: &HashParam,
         cu: &(Option<u32>, Option<u64>),
         address_lookup_tables: &[AddressLookupTableAccount],
         memo: Option<Vec<&str>>,
-    ) -> Result<TxEnvelope<'a, Self>, Box<dyn std::error::Error + Send + Sync>>
+    ) -> Result<TxEnvelope<'a, Self>, BuildError>
     where
         Self: Sync + Send + Sized + Display + SendTxEncoded + BuildTx,
     {
@@ -541,14 +736,9 @@ pub trait BuildV0Tx {
             if let Some(cu_price) = cu.1 {
                 instructions.push(ComputeBudgetInstruction::set_compute_unit_price(cu_price));
             }
-            if self.uses_tip_transfer() {
-                if tip != &Some(0) {
-                    let tip_address = self.get_tip_address();
-                    let tip_amt = tip.unwrap_or(self.get_min_tip_amount());
-                    if tip_amt > 0 {
-                        instructions.push(transfer(&payer, &tip_address, tip_amt));
-                    }
-                }
+            let tip_amt = self.resolve_tip(tip);
+            if tip_amt > 0 {
+                instructions.push(transfer(&payer, &self.get_tip_address(), tip_amt));
             }
             if let Some(memo_str) = memo {
                 let memo_concat = memo_str.join("-");
@@ -560,18 +750,20 @@ pub trait BuildV0Tx {
             }
             instructions.extend(ixs.iter().cloned());
 
-            let message = V0Message::try_compile(&payer, &instructions, address_lookup_tables, hash)?;
+            let message = V0Message::try_compile(&payer, &instructions, address_lookup_tables, hash)
+                .map_err(|e| BuildError::Compile(e.to_string()))?;
             let transaction = VersionedTransaction::try_new(
                 solana_sdk::message::VersionedMessage::V0(message),
                 signers,
-            )?;
+            )
+            .map_err(|e| BuildError::Sign(e.to_string()))?;
             let sig = transaction.signatures[0];
             info!("  sig: {}", sig);
             Ok(TxEnvelope {
                 tx: DetailedTx {
                     tx: SolTx::V0(transaction),
                     platform: self.platform(),
-                    tip: *tip,
+                    tip: (tip_amt > 0).then_some(tip_amt),
                     cu_limit: cu.0,
                     cu_price: cu.1,
                 },
@@ -588,6 +780,7 @@ impl BuildV0Tx for blockrazor::Blockrazor {}
 impl BuildV0Tx for helius::Helius {}
 impl BuildV0Tx for harmonic::HarmonicBlockEngine {}
 impl BuildV0Tx for jito::Jito {}
+impl BuildV0Tx for jito_grpc::JitoGrpc {}
 impl BuildV0Tx for nodeone::NodeOne {}
 impl BuildV0Tx for temporal::Temporal {}
 impl BuildV0Tx for zeroslot::ZeroSlot {}
@@ -597,6 +790,48 @@ impl BuildV0Tx for stellium::Stellium {}
 impl BuildV0Tx for ever_stake::EverStake {}
 impl BuildV0Tx for ever_stake_quic::EverStakeQuic {}
 
+/// 对象安全的发送者抽象：组装（含平台 tip 账户）+ 发送。
+///
+/// 所有实现了 `BuildV0Tx` 的平台客户端自动实现，
+/// 可放入 `Vec<Box<dyn Sender>>` / `Vec<Arc<dyn Sender>>`，在运行时按配置决定启用哪些平台。
+pub trait Sender: BuildTx + SendTxEncoded + Display + Send + Sync {
+    /// 组装 V0 交易，参数含义同 `BuildV0Tx::build_v0_tx`
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        &self,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        tip: &TipStrategy,
+        nonce: &HashParam,
+        cu: &(Option<u32>, Option<u64>),
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> Result<TxEnvelope<'_, dyn Sender>, BuildError>;
+}
+
+impl<T> Sender for T
+where
+    T: BuildV0Tx + BuildTx + SendTxEncoded + Display + Send + Sync,
+{
+    fn build(
+        &self,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        tip: &TipStrategy,
+        nonce: &HashParam,
+        cu: &(Option<u32>, Option<u64>),
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> Result<TxEnvelope<'_, dyn Sender>, BuildError> {
+        let envelope =
+            self.build_v0_tx(ixs, signer, tip, nonce, cu, address_lookup_tables, memo)?;
+        Ok(TxEnvelope {
+            tx: envelope.tx,
+            sender: self,
+        })
+    }
+}
+
 #[test]
 fn test_region() {
     let regions = &[
@@ -616,3 +851,129 @@ fn test_region() {
         println!("{}, {:?}", region, Region::from(region));
     }
 }
+
+#[tokio::test]
+async fn test_send_propagates_platform_error() {
+    struct Rejecting;
+
+    #[async_trait::async_trait]
+    impl SendTxEncoded for Rejecting {
+        async fn send_tx_encoded(&self, _tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+            Err(SendError::Rejected {
+                code: Some(-32002),
+                message: "blockhash not found".to_string(),
+            })
+        }
+    }
+
+    let payer = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    );
+    let envelope = TxEnvelope {
+        tx: DetailedTx {
+            tx: SolTx::Legacy(tx),
+            platform: PlatformName::Jito,
+            tip: None,
+            cu_limit: None,
+            cu_price: None,
+        },
+        sender: &Rejecting,
+    };
+
+    let outcome = envelope.submit().await;
+    assert_eq!(outcome.sig, envelope.sig());
+    assert!(!outcome.is_accepted());
+    assert!(matches!(
+        envelope.send().await,
+        Err(SendError::Rejected {
+            code: Some(-32002),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_dyn_sender_collection() {
+    let senders: Vec<Box<dyn Sender>> = vec![
+        Box::new(jito::Jito::init_with("", Region::Frankfurt)),
+        Box::new(helius::Helius::init_with("", Region::Frankfurt)),
+        Box::new(zeroslot::ZeroSlot::init_with("", Region::Frankfurt)),
+    ];
+    let signer = Arc::new(Keypair::new());
+    let ix = transfer(&signer.pubkey(), &Pubkey::new_unique(), 1);
+
+    for sender in &senders {
+        let envelope = sender
+            .build(
+                std::slice::from_ref(&ix),
+                &signer,
+                &TipStrategy::Min,
+                &HashParam::Blockhash(Hash::default()),
+                &(None, None),
+                &[],
+                None,
+            )
+            .unwrap();
+        assert_eq!(envelope.tx.platform, sender.platform());
+        assert_eq!(envelope.sender.platform(), sender.platform());
+    }
+}
+
+#[tokio::test]
+async fn test_json_rpc_bundle_clients() {
+    let url = crate::test_utils::mock_json_rpc(|req| {
+        assert_eq!(req["method"], "sendBundle");
+        serde_json::json!("bundle-1")
+    })
+    .await;
+
+    let payer = Keypair::new();
+    let txs = vec![SolTx::Legacy(Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    ))];
+
+    let mut nodeone = nodeone::NodeOne::init_with("key", Region::Frankfurt);
+    nodeone.endpoint = url.clone();
+    let mut temporal = temporal::Temporal::init_with("key", Region::Frankfurt);
+    temporal.endpoint = url.clone();
+    let mut zeroslot = zeroslot::ZeroSlot::init_with("key", Region::Frankfurt);
+    zeroslot.endpoint = url.clone();
+
+    let receipts = [
+        nodeone.build_bundle(&txs).send_bundle().await.unwrap(),
+        temporal.build_bundle(&txs).send_bundle().await.unwrap(),
+        zeroslot.build_bundle(&txs).send_bundle().await.unwrap(),
+    ];
+    for receipt in &receipts {
+        assert_eq!(receipt.server_id.as_deref(), Some("bundle-1"));
+    }
+
+    // 交易数不合法时不发请求
+    assert!(matches!(
+        nodeone.build_bundle(&[]).send_bundle().await,
+        Err(SendError::BundleSize {
+            count: 0,
+            min: 1,
+            max: MAX_BUNDLE_TXS
+        })
+    ));
+    let too_many = vec![txs[0].clone(); MAX_BUNDLE_TXS + 1];
+    assert!(matches!(
+        temporal.build_bundle(&too_many).send_bundle().await,
+        Err(SendError::BundleSize { count, .. }) if count == MAX_BUNDLE_TXS + 1
+    ));
+
+    // result 不是字符串时返回 Parse，而不是把 JSON 当作 bundle id
+    zeroslot.endpoint = crate::test_utils::mock_json_rpc(|_| serde_json::json!({})).await;
+    assert!(matches!(
+        zeroslot.build_bundle(&txs).send_bundle().await,
+        Err(SendError::Parse(_))
+    ));
+}
@@ -6,16 +6,18 @@ impl fmt::Display for NextBlock {
 }
 
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SubmitReceipt};
+use crate::tip_accounts;
 
 // NextBlock MEV 保护和 tip 地址
 pub const NEXTBLOCK_TIP_ACCOUNTS: &[Pubkey] = &[
@@ -102,10 +104,11 @@ impl NextBlock {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for NextBlock {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("next block send: ", {
             let url = format!("{}/api/v2/submit", self.endpoint);
 
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -120,19 +123,23 @@ impl crate::platform_clients::SendTxEncoded for NextBlock {
                 .send()
                 .await;
 
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("NextBlock send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("NextBlock send error: {}", e);
+            })?;
+            let latency = started.elapsed();
 
             info!("NextBlock response: {}", response);
-            Ok(())
+            let signature = serde_json::from_str::<serde_json::Value>(&response)
+                .ok()
+                .and_then(|v| v.get("signature")?.as_str().map(str::to_string));
+            Ok(SubmitReceipt {
+                platform: PlatformName::Nextblock,
+                endpoint: url,
+                latency,
+                http_status: Some(status),
+                server_id: signature,
+                raw: Some(response),
+            })
         })
     }
 }
@@ -142,13 +149,14 @@ impl crate::platform_clients::SendBundle for NextBlock {
     async fn send_bundle(
         &self,
         txs: &[crate::platform_clients::SolTx],
-    ) -> Result<Vec<solana_sdk::signature::Signature>, String> {
+    ) -> Result<SubmitReceipt, SendError> {
         // NextBlock 要求 2-4 笔交易
         if txs.len() < 2 || txs.len() > 4 {
-            return Err(format!(
-                "NextBlock bundle requires 2-4 transactions, got {}",
-                txs.len()
-            ));
+            return Err(SendError::BundleSize {
+                count: txs.len(),
+                min: 2,
+                max: 4,
+            });
         }
 
         let url = format!("{}/api/v2/submit-batch", self.endpoint);
@@ -156,9 +164,7 @@ impl crate::platform_clients::SendBundle for NextBlock {
         // 构建 entries 数组
         let mut entries = Vec::new();
         for tx in txs {
-            let tx_base64 = tx
-                .to_base64()
-                .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+            let tx_base64 = tx.to_base64()?;
             entries.push(json!({
                 "transaction": {
                     "content": tx_base64
@@ -170,6 +176,7 @@ impl crate::platform_clients::SendBundle for NextBlock {
             "entries": entries
         });
 
+        let started = Instant::now();
         let res = self
             .http_client
             .post(&url)
@@ -179,48 +186,47 @@ impl crate::platform_clients::SendBundle for NextBlock {
             .send()
             .await;
 
-        let response = match res {
-            Ok(resp) => match resp.text().await {
-                Ok(text) => text,
-                Err(e) => return Err(format!("response text error: {}", e)),
-            },
-            Err(e) => {
-                log::error!("NextBlock bundle send error: {:?}", e);
-                return Err(format!("bundle send error: {}", e));
-            }
-        };
+        let (status, response) = read_http_response(res).await.inspect_err(|e| {
+            log::error!("NextBlock bundle send error: {}", e);
+        })?;
+        let latency = started.elapsed();
 
         info!("NextBlock bundle response: {}", response);
 
         // 解析响应
         let parsed_response: serde_json::Value = serde_json::from_str(&response)
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| SendError::Parse(format!("{}, raw: {}", e, response)))?;
 
         if let Some(signature_str) = parsed_response.get("signature").and_then(|s| s.as_str()) {
-            // NextBlock 只返回一个签名，我们需要为每个交易返回相同的签名
-            let signature = signature_str
-                .parse::<solana_sdk::signature::Signature>()
-                .map_err(|e| format!("Failed to parse signature: {}", e))?;
-
-            // 返回与交易数量相同的签名数组
-            Ok(vec![signature; txs.len()])
+            // NextBlock 只返回一个签名，作为 bundle 的 server id
+            Ok(SubmitReceipt {
+                platform: PlatformName::Nextblock,
+                endpoint: url,
+                latency,
+                http_status: Some(status),
+                server_id: Some(signature_str.to_string()),
+                raw: Some(response),
+            })
         } else if let Some(error_msg) = parsed_response.get("message").and_then(|m| m.as_str()) {
-            Err(format!("NextBlock error: {}", error_msg))
+            Err(SendError::Rejected {
+                code: parsed_response.get("code").and_then(|c| c.as_i64()),
+                message: error_msg.to_string(),
+            })
         } else {
-            Err("Invalid response format from NextBlock".to_string())
+            Err(SendError::Parse(format!(
+                "Invalid response format from NextBlock: {}",
+                response
+            )))
         }
     }
 }
 
 impl crate::platform_clients::BuildTx for NextBlock {
     fn get_tip_address(&self) -> Pubkey {
-        *NEXTBLOCK_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| NEXTBLOCK_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Nextblock, NEXTBLOCK_TIP_ACCOUNTS)
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        NEXTBLOCK_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Nextblock, NEXTBLOCK_TIP_ACCOUNTS)
     }
     fn platform(&self) -> PlatformName {
         PlatformName::Nextblock
@@ -230,5 +236,8 @@ impl crate::platform_clients::BuildTx for NextBlock {
         Self::MIN_TIP_AMOUNT_TX
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
@@ -5,16 +5,18 @@ impl fmt::Display for NodeOne {
     }
 }
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SolTx, SubmitReceipt, send_json_rpc_bundle};
+use crate::tip_accounts;
 
 pub const NODEONE_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("node1PqAa3BWWzUnTHVbw8NJHC874zn9ngAkXjgWEej"),
@@ -88,8 +90,9 @@ impl NodeOne {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for NodeOne {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("node1 send: ", {
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&self.endpoint)
@@ -103,28 +106,40 @@ impl crate::platform_clients::SendTxEncoded for NodeOne {
                 }))
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("node1 send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             info!("node1: {}", response);
-            Ok(())
+            let signature = parse_json_rpc_result(&response)?;
+            Ok(SubmitReceipt {
+                platform: PlatformName::Nodeone,
+                endpoint: self.endpoint.clone(),
+                latency,
+                http_status: Some(status),
+                server_id: signature.as_str().map(str::to_string),
+                raw: Some(response),
+            })
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::platform_clients::SendBundle for NodeOne {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        log_time!("node1 bundle send: ", {
+            let request = self
+                .http_client
+                .post(&self.endpoint)
+                .header("api-key", self.auth_token.as_str());
+            send_json_rpc_bundle(PlatformName::Nodeone, request, &self.endpoint, txs).await
         })
     }
 }
 
 impl crate::platform_clients::BuildTx for NodeOne {
     fn get_tip_address(&self) -> Pubkey {
-        *NODEONE_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| NODEONE_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Nodeone, NODEONE_TIP_ACCOUNTS)
     }
     fn platform(&self) -> PlatformName {
         PlatformName::Nodeone
@@ -134,7 +149,22 @@ impl crate::platform_clients::BuildTx for NodeOne {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        NODEONE_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Nodeone, NODEONE_TIP_ACCOUNTS)
+    }
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
     }
     // 使用默认实现，无需重写 build_tx
 }
+
+impl crate::platform_clients::BuildBundle for NodeOne {
+    fn build_bundle<'a>(
+        &'a self,
+        txs: &[SolTx],
+    ) -> crate::platform_clients::BundleEnvelope<'a, NodeOne> {
+        crate::platform_clients::BundleEnvelope {
+            txs: txs.to_vec(),
+            sender: self,
+        }
+    }
+}
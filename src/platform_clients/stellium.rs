@@ -5,16 +5,18 @@ impl fmt::Display for Stellium {
     }
 }
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SubmitReceipt};
+use crate::tip_accounts;
 
 // Stellium tip 地址
 pub const STELLIUM_TIP_ACCOUNTS: &[Pubkey] = &[
@@ -90,7 +92,7 @@ impl Stellium {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for Stellium {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("stellium send: ", {
             // URL 格式：https://STELLIUM_ENDPOINT/$APIKEY
             let url = format!("{}/{}", self.endpoint, self.api_key);
@@ -102,6 +104,7 @@ impl crate::platform_clients::SendTxEncoded for Stellium {
                 .as_nanos()
                 .to_string();
 
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -118,42 +121,31 @@ impl crate::platform_clients::SendTxEncoded for Stellium {
                 .send()
                 .await;
 
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("Stellium send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("Stellium send error: {}", e);
+            })?;
+            let latency = started.elapsed();
 
             info!("Stellium response: {}", response);
 
-            // 解析响应
-            let parsed_response: serde_json::Value = serde_json::from_str(&response)
-                .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-            // 检查是否有错误
-            if let Some(error) = parsed_response.get("error") {
-                let error_code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
-                let error_message = error
-                    .get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or("Unknown error");
-                return Err(format!(
-                    "Stellium error (code {}): {}",
-                    error_code, error_message
-                ));
-            }
-
-            // 检查是否有结果
-            if let Some(signature) = parsed_response.get("result").and_then(|r| r.as_str()) {
-                info!("Stellium transaction signature: {}", signature);
-                Ok(())
-            } else {
-                Err("Invalid response format from Stellium".to_string())
+            // 解析响应，error 字段映射为带错误码的 Rejected
+            let signature = parse_json_rpc_result(&response)?;
+            match signature.as_str() {
+                Some(signature) => {
+                    info!("Stellium transaction signature: {}", signature);
+                    Ok(SubmitReceipt {
+                        platform: PlatformName::Stellium,
+                        endpoint: self.endpoint.clone(),
+                        latency,
+                        http_status: Some(status),
+                        server_id: Some(signature.to_string()),
+                        raw: Some(response),
+                    })
+                }
+                None => Err(SendError::Parse(format!(
+                    "Invalid response format from Stellium: {}",
+                    response
+                ))),
             }
         })
     }
@@ -161,10 +153,7 @@ impl crate::platform_clients::SendTxEncoded for Stellium {
 
 impl crate::platform_clients::BuildTx for Stellium {
     fn get_tip_address(&self) -> Pubkey {
-        *STELLIUM_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| STELLIUM_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Stellium, STELLIUM_TIP_ACCOUNTS)
     }
 
     fn platform(&self) -> PlatformName {
@@ -176,8 +165,11 @@ impl crate::platform_clients::BuildTx for Stellium {
     }
 
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        STELLIUM_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Stellium, STELLIUM_TIP_ACCOUNTS)
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
@@ -1,14 +1,16 @@
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SolTx, SubmitReceipt, send_json_rpc_bundle};
+use crate::tip_accounts;
 
 pub const TEMPORAL_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("TEMPaMeCRFAS9EKF53Jd6KpHxgL47uWLcpFArU1Fanq"),
@@ -95,12 +97,13 @@ impl Temporal {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for Temporal {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("temproal send: ", {
             let mut url = String::with_capacity(self.endpoint.len() + self.token.len() + 20);
             url.push_str(&self.endpoint);
             url.push_str("?c=");
             url.push_str(&self.token);
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -119,28 +122,39 @@ impl crate::platform_clients::SendTxEncoded for Temporal {
                 }))
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("temporal send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             info!("temporal: {}", response);
-            Ok(())
+            let signature = parse_json_rpc_result(&response)?;
+            Ok(SubmitReceipt {
+                platform: PlatformName::Temporal,
+                endpoint: self.endpoint.clone(),
+                latency,
+                http_status: Some(status),
+                server_id: signature.as_str().map(str::to_string),
+                raw: Some(response),
+            })
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::platform_clients::SendBundle for Temporal {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        log_time!("temporal bundle send: ", {
+            let request = self
+                .http_client
+                .post(format!("{}?c={}", self.endpoint, self.token));
+            send_json_rpc_bundle(PlatformName::Temporal, request, &self.endpoint, txs).await
         })
     }
 }
 
 impl crate::platform_clients::BuildTx for Temporal {
     fn get_tip_address(&self) -> Pubkey {
-        *TEMPORAL_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| TEMPORAL_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Temporal, TEMPORAL_TIP_ACCOUNTS)
     }
     fn platform(&self) -> PlatformName {
         PlatformName::Temporal
@@ -151,9 +165,12 @@ impl crate::platform_clients::BuildTx for Temporal {
     }
 
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        TEMPORAL_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Temporal, TEMPORAL_TIP_ACCOUNTS)
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
 
@@ -163,3 +180,15 @@ impl fmt::Display for Temporal {
         write!(f, "Temporal")
     }
 }
+
+impl crate::platform_clients::BuildBundle for Temporal {
+    fn build_bundle<'a>(
+        &'a self,
+        txs: &[SolTx],
+    ) -> crate::platform_clients::BundleEnvelope<'a, Temporal> {
+        crate::platform_clients::BundleEnvelope {
+            txs: txs.to_vec(),
+            sender: self,
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use log::info;
-use rand::seq::IndexedRandom;
 use reqwest::Client;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use utils::log_time;
 
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
 use crate::constants::{HTTP_CLIENT, REGION};
-use crate::platform_clients::{PlatformName, Region};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{PlatformName, Region, SolTx, SubmitReceipt, send_json_rpc_bundle};
+use crate::tip_accounts;
 
 pub const ZEROSLOT_TIP_ACCOUNTS: &[Pubkey] = &[
     pubkey!("6fQaVhYZA4w3MBSXjJ81Vf6W1EDYeUPXpgVQ6UQyU1Av"),
@@ -94,12 +96,13 @@ impl ZeroSlot {
 
 #[async_trait::async_trait]
 impl crate::platform_clients::SendTxEncoded for ZeroSlot {
-    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<(), String> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
         log_time!("0slot send: ", {
             let mut url = String::new();
             url.push_str(&self.endpoint);
             url.push_str("?api-key=");
             url.push_str(&self.token);
+            let started = Instant::now();
             let res = self
                 .http_client
                 .post(&url)
@@ -118,28 +121,39 @@ impl crate::platform_clients::SendTxEncoded for ZeroSlot {
                 }))
                 .send()
                 .await;
-            let response = match res {
-                Ok(resp) => match resp.text().await {
-                    Ok(text) => text,
-                    Err(e) => return Err(format!("response text error: {}", e)),
-                },
-                Err(e) => {
-                    log::error!("send error: {:?}", e);
-                    return Err(format!("send error: {}", e));
-                }
-            };
+            let (status, response) = read_http_response(res).await.inspect_err(|e| {
+                log::error!("zeroslot send error: {}", e);
+            })?;
+            let latency = started.elapsed();
             info!("zeroslot: {}", response);
-            Ok(())
+            let signature = parse_json_rpc_result(&response)?;
+            Ok(SubmitReceipt {
+                platform: PlatformName::Zeroslot,
+                endpoint: self.endpoint.clone(),
+                latency,
+                http_status: Some(status),
+                server_id: signature.as_str().map(str::to_string),
+                raw: Some(response),
+            })
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::platform_clients::SendBundle for ZeroSlot {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        log_time!("zeroslot bundle send: ", {
+            let request = self
+                .http_client
+                .post(format!("{}?api-key={}", self.endpoint, self.token));
+            send_json_rpc_bundle(PlatformName::Zeroslot, request, &self.endpoint, txs).await
         })
     }
 }
 
 impl crate::platform_clients::BuildTx for ZeroSlot {
     fn get_tip_address(&self) -> Pubkey {
-        *ZEROSLOT_TIP_ACCOUNTS
-            .choose(&mut rand::rng())
-            .or_else(|| ZEROSLOT_TIP_ACCOUNTS.first())
-            .unwrap()
+        tip_accounts::tip_address(PlatformName::Zeroslot, ZEROSLOT_TIP_ACCOUNTS)
     }
     fn platform(&self) -> PlatformName {
         PlatformName::Zeroslot
@@ -148,9 +162,12 @@ impl crate::platform_clients::BuildTx for ZeroSlot {
         Self::MIN_TIP_AMOUNT_TX
     }
     fn tip_recvs(&self) -> Vec<Pubkey> {
-        ZEROSLOT_TIP_ACCOUNTS.to_vec()
+        tip_accounts::tip_recvs(PlatformName::Zeroslot, ZEROSLOT_TIP_ACCOUNTS)
     }
 
+    fn default_tps(&self) -> Option<u64> {
+        Some(Self::DEFAULT_TPS)
+    }
     // 使用默认实现，无需重写 build_tx
 }
 
@@ -159,3 +176,15 @@ impl std::fmt::Display for ZeroSlot {
         write!(f, "ZeroSlot")
     }
 }
+
+impl crate::platform_clients::BuildBundle for ZeroSlot {
+    fn build_bundle<'a>(
+        &'a self,
+        txs: &[SolTx],
+    ) -> crate::platform_clients::BundleEnvelope<'a, ZeroSlot> {
+        crate::platform_clients::BundleEnvelope {
+            txs: txs.to_vec(),
+            sender: self,
+        }
+    }
+}
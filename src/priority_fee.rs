@@ -0,0 +1,356 @@
+//! Priority fee（`cu_price`）估算
+//!
+//! 以交易的可写账户查询近期 priority fee，返回分档 / 百分位的 micro-lamports 值。
+//! 来源：标准 RPC 的 `getRecentPrioritizationFees`，或 Helius 的 `getPriorityFeeEstimate`。
+
+use std::sync::Arc;
+
+use ahash::AHashSet;
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::{Value, json};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::constants::{HTTP_CLIENT, JSON_RPC_URL};
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+
+/// `getRecentPrioritizationFees` 最多接受 128 个账户
+const MAX_ACCOUNTS_PER_REQUEST: usize = 128;
+
+/// 估算档位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityLevel {
+    /// 约 25 百分位
+    Low,
+    /// 约 50 百分位
+    Medium,
+    /// 约 75 百分位
+    High,
+    /// 约 95 百分位
+    VeryHigh,
+    /// 任意百分位（0-100）
+    Percentile(u8),
+}
+
+/// 一次估算结果（micro-lamports / CU）
+#[derive(Debug, Clone, Default)]
+pub struct PriorityFeeEstimate {
+    pub low: u64,
+    pub medium: u64,
+    pub high: u64,
+    pub very_high: u64,
+    /// 原始样本（升序）；只给出分档的来源（如 Helius）为空
+    pub samples: Vec<u64>,
+}
+
+impl PriorityFeeEstimate {
+    /// 由原始样本计算各档位
+    pub fn from_samples(mut samples: Vec<u64>) -> Self {
+        samples.sort_unstable();
+        Self {
+            low: percentile_of(&samples, 25),
+            medium: percentile_of(&samples, 50),
+            high: percentile_of(&samples, 75),
+            very_high: percentile_of(&samples, 95),
+            samples,
+        }
+    }
+
+    /// 取某个档位；没有原始样本时 `Percentile` 按所在区间取最接近的档位
+    pub fn get(&self, level: PriorityLevel) -> u64 {
+        match level {
+            PriorityLevel::Low => self.low,
+            PriorityLevel::Medium => self.medium,
+            PriorityLevel::High => self.high,
+            PriorityLevel::VeryHigh => self.very_high,
+            PriorityLevel::Percentile(p) if !self.samples.is_empty() => {
+                percentile_of(&self.samples, p)
+            }
+            PriorityLevel::Percentile(p) => match p {
+                0..=25 => self.low,
+                26..=50 => self.medium,
+                51..=75 => self.high,
+                _ => self.very_high,
+            },
+        }
+    }
+}
+
+/// 升序样本的最近秩百分位，空样本返回 0
+fn percentile_of(sorted: &[u64], percentile: u8) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percentile.min(100) as usize * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// 指令中所有可写账户（去重，保持顺序）
+pub fn writable_accounts(ixs: &[Instruction]) -> Vec<Pubkey> {
+    let mut seen = AHashSet::new();
+    ixs.iter()
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|meta| meta.is_writable && seen.insert(meta.pubkey))
+        .map(|meta| meta.pubkey)
+        .collect()
+}
+
+/// priority fee 估算来源
+#[async_trait::async_trait]
+pub trait PriorityFeeEstimator: Send + Sync {
+    async fn estimate(
+        &self,
+        writable_accounts: &[Pubkey],
+    ) -> Result<PriorityFeeEstimate, SendError>;
+}
+
+/// 标准 RPC `getRecentPrioritizationFees`，取最近 150 个 slot 的样本
+pub struct RpcPriorityFee {
+    rpc_url: String,
+    http_client: Arc<Client>,
+}
+
+impl RpcPriorityFee {
+    /// 使用 `JSON_RPC_URL`
+    pub fn new() -> Self {
+        Self::init_with(JSON_RPC_URL.clone())
+    }
+
+    pub fn init_with(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            http_client: Arc::clone(&HTTP_CLIENT),
+        }
+    }
+}
+
+impl Default for RpcPriorityFee {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PriorityFeeEstimator for RpcPriorityFee {
+    async fn estimate(
+        &self,
+        writable_accounts: &[Pubkey],
+    ) -> Result<PriorityFeeEstimate, SendError> {
+        let accounts: Vec<String> = writable_accounts
+            .iter()
+            .take(MAX_ACCOUNTS_PER_REQUEST)
+            .map(|a| a.to_string())
+            .collect();
+        let res = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getRecentPrioritizationFees",
+                "params": [accounts],
+            }))
+            .send()
+            .await;
+        let (_, body) = read_http_response(res).await?;
+        let result = parse_json_rpc_result(&body)?;
+        let estimate = parse_recent_prioritization_fees(&result)?;
+        info!(
+            "[RpcPriorityFee] {} samples, medium {} high {}",
+            estimate.samples.len(),
+            estimate.medium,
+            estimate.high
+        );
+        Ok(estimate)
+    }
+}
+
+/// 解析 `[{"slot": 1, "prioritizationFee": 100}, ...]`
+fn parse_recent_prioritization_fees(result: &Value) -> Result<PriorityFeeEstimate, SendError> {
+    let entries = result
+        .as_array()
+        .ok_or_else(|| SendError::Parse(format!("expected array, raw: {}", result)))?;
+    let samples = entries
+        .iter()
+        .map(|entry| {
+            entry["prioritizationFee"]
+                .as_u64()
+                .ok_or_else(|| SendError::Parse(format!("missing prioritizationFee: {}", entry)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(PriorityFeeEstimate::from_samples(samples))
+}
+
+/// Helius `getPriorityFeeEstimate`，`rpc_url` 需带 api-key
+pub struct HeliusPriorityFee {
+    rpc_url: String,
+    http_client: Arc<Client>,
+}
+
+impl HeliusPriorityFee {
+    pub fn init_with(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            http_client: Arc::clone(&HTTP_CLIENT),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriorityFeeEstimator for HeliusPriorityFee {
+    async fn estimate(
+        &self,
+        writable_accounts: &[Pubkey],
+    ) -> Result<PriorityFeeEstimate, SendError> {
+        let accounts: Vec<String> = writable_accounts.iter().map(|a| a.to_string()).collect();
+        let res = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getPriorityFeeEstimate",
+                "params": [{
+                    "accountKeys": accounts,
+                    "options": { "includeAllPriorityFeeLevels": true },
+                }],
+            }))
+            .send()
+            .await;
+        let (_, body) = read_http_response(res).await?;
+        let result = parse_json_rpc_result(&body)?;
+        let estimate = parse_helius_fee_levels(&result)?;
+        info!(
+            "[HeliusPriorityFee] medium {} high {}",
+            estimate.medium, estimate.high
+        );
+        Ok(estimate)
+    }
+}
+
+/// 解析 `{"priorityFeeLevels": {"low": 1.0, "medium": 2.0, ...}}`，值可能是小数
+fn parse_helius_fee_levels(result: &Value) -> Result<PriorityFeeEstimate, SendError> {
+    let levels = &result["priorityFeeLevels"];
+    let level = |field: &str| {
+        levels[field]
+            .as_f64()
+            .map(|v| v.ceil() as u64)
+            .ok_or_else(|| {
+                SendError::Parse(format!("missing priorityFeeLevels.{}: {}", field, result))
+            })
+    };
+    Ok(PriorityFeeEstimate {
+        low: level("low")?,
+        medium: level("medium")?,
+        high: level("high")?,
+        very_high: level("veryHigh")?,
+        samples: Vec::new(),
+    })
+}
+
+/// 自动 `cu_price`：按指令的可写账户估算，取指定档位，可设上限
+#[derive(Clone)]
+pub struct AutoCuPrice {
+    estimator: Arc<dyn PriorityFeeEstimator>,
+    level: PriorityLevel,
+    cap: Option<u64>,
+}
+
+impl AutoCuPrice {
+    pub fn new(
+        estimator: Arc<dyn PriorityFeeEstimator>,
+        level: PriorityLevel,
+        cap: Option<u64>,
+    ) -> Self {
+        Self {
+            estimator,
+            level,
+            cap,
+        }
+    }
+
+    /// 估算 `ixs` 的 `cu_price`
+    pub async fn cu_price(&self, ixs: &[Instruction]) -> Result<u64, SendError> {
+        let estimate = self.estimator.estimate(&writable_accounts(ixs)).await?;
+        let price = estimate.get(self.level);
+        Ok(self.cap.map_or(price, |cap| price.min(cap)))
+    }
+
+    /// 补全 `cu` 参数：调用方已给出 `cu_price` 时原样返回；估算失败时记录日志并保持 `None`
+    pub async fn fill(
+        &self,
+        ixs: &[Instruction],
+        cu: &(Option<u32>, Option<u64>),
+    ) -> (Option<u32>, Option<u64>) {
+        if cu.1.is_some() {
+            return *cu;
+        }
+        match self.cu_price(ixs).await {
+            Ok(price) => (cu.0, Some(price)),
+            Err(e) => {
+                warn!(
+                    "[AutoCuPrice] estimate failed, build without cu_price: {}",
+                    e
+                );
+                *cu
+            }
+        }
+    }
+}
+
+#[test]
+fn test_priority_fee_estimate() {
+    use solana_sdk::instruction::AccountMeta;
+
+    let result = json!(
+        (1..=100u64)
+            .map(|fee| json!({ "slot": 1000 + fee, "prioritizationFee": fee * 10 }))
+            .collect::<Vec<_>>()
+    );
+    let estimate = parse_recent_prioritization_fees(&result).unwrap();
+    assert_eq!(estimate.low, 250);
+    assert_eq!(estimate.medium, 500);
+    assert_eq!(estimate.high, 750);
+    assert_eq!(estimate.very_high, 950);
+    assert_eq!(estimate.get(PriorityLevel::Percentile(90)), 900);
+    assert_eq!(estimate.get(PriorityLevel::Percentile(100)), 1000);
+    assert_eq!(PriorityFeeEstimate::from_samples(Vec::new()).medium, 0);
+
+    let helius = parse_helius_fee_levels(&json!({
+        "priorityFeeLevels": {
+            "min": 0.0,
+            "low": 10.5,
+            "medium": 200.0,
+            "high": 3000.0,
+            "veryHigh": 40000.0,
+            "unsafeMax": 500000.0,
+        }
+    }))
+    .unwrap();
+    assert_eq!(helius.low, 11);
+    assert_eq!(helius.get(PriorityLevel::Percentile(60)), 3000);
+    assert!(parse_helius_fee_levels(&json!({})).is_err());
+
+    let (a, b, c) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ixs = [
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![
+                AccountMeta::new(a, true),
+                AccountMeta::new_readonly(b, false),
+            ],
+        ),
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(c, false), AccountMeta::new(a, false)],
+        ),
+    ];
+    assert_eq!(writable_accounts(&ixs), vec![a, c]);
+}
@@ -0,0 +1,289 @@
+//! 按平台 + API key 的令牌桶限速
+//!
+//! 速率默认取客户端的 `DEFAULT_TPS`（`BuildTx::default_tps`），可按平台或按 key 覆盖。
+//! 同一平台、同一 key 的多个客户端共用一个令牌桶。
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use ahash::AHashMap;
+use solana_sdk::pubkey::Pubkey;
+use tokio::time::{Duration, Instant};
+
+use crate::error::SendError;
+use crate::platform_clients::{
+    BuildTx, BuildV0Tx, PlatformName, SendBundle, SendTxEncoded, SolTx, SubmitReceipt,
+};
+
+/// 没有令牌时的行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// 排队等待令牌
+    Wait,
+    /// 立即返回 `SendError::RateLimited`
+    Reject,
+}
+
+/// 令牌桶，容量等于每秒速率（允许 1 秒的突发）
+#[derive(Debug)]
+struct Bucket {
+    tps: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(tps: u64) -> Self {
+        let tps = tps.max(1) as f64;
+        Self {
+            tps,
+            tokens: tps,
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.tps).min(self.tps);
+        self.updated = now;
+    }
+
+    /// 取一个令牌，成功返回 `None`；`reserve` 为 true 时令牌不足也预扣，并返回需要等待的时长
+    fn take(&mut self, reserve: bool) -> Option<Duration> {
+        self.refill(Instant::now());
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return None;
+        }
+        let wait = Duration::from_secs_f64((1.0 - self.tokens) / self.tps);
+        if reserve {
+            self.tokens -= 1.0;
+        }
+        Some(wait)
+    }
+}
+
+/// 限速器，按 `(平台, key)` 维护令牌桶
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: Mutex<AHashMap<(PlatformName, String), Bucket>>,
+    /// 按平台覆盖默认 TPS
+    platform_tps: Mutex<AHashMap<PlatformName, u64>>,
+    /// 按 `(平台, key)` 覆盖，优先于平台覆盖
+    key_tps: Mutex<AHashMap<(PlatformName, String), u64>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// 覆盖某个平台所有 key 的 TPS（单独覆盖过的 key 除外），已创建的令牌桶会被重置
+    pub fn set_platform_tps(&self, platform: PlatformName, tps: u64) {
+        self.platform_tps.lock().unwrap().insert(platform, tps);
+        self.buckets
+            .lock()
+            .unwrap()
+            .retain(|(p, _), _| *p != platform);
+    }
+
+    /// 覆盖某个平台下单个 key 的 TPS
+    pub fn set_key_tps(&self, platform: PlatformName, key: impl Into<String>, tps: u64) {
+        let key = (platform, key.into());
+        self.key_tps.lock().unwrap().insert(key.clone(), tps);
+        self.buckets.lock().unwrap().insert(key, Bucket::new(tps));
+    }
+
+    /// 获取一个发送许可。`default_tps` 为 `None` 且未被覆盖时不限速
+    pub async fn acquire(
+        &self,
+        platform: PlatformName,
+        key: &str,
+        default_tps: Option<u64>,
+        mode: RateLimitMode,
+    ) -> Result<(), SendError> {
+        let wait = {
+            let mut buckets = self.buckets.lock().unwrap();
+            let id = (platform, key.to_string());
+            let bucket = match buckets.get_mut(&id) {
+                Some(bucket) => bucket,
+                None => {
+                    let tps = self
+                        .key_tps
+                        .lock()
+                        .unwrap()
+                        .get(&id)
+                        .copied()
+                        .or_else(|| self.platform_tps.lock().unwrap().get(&platform).copied())
+                        .or(default_tps);
+                    let Some(tps) = tps else {
+                        return Ok(());
+                    };
+                    buckets.entry(id).or_insert_with(|| Bucket::new(tps))
+                }
+            };
+            bucket.take(mode == RateLimitMode::Wait)
+        };
+
+        match (wait, mode) {
+            (None, _) => Ok(()),
+            (Some(wait), RateLimitMode::Wait) => {
+                tokio::time::sleep(wait).await;
+                Ok(())
+            }
+            (Some(wait), RateLimitMode::Reject) => Err(SendError::RateLimited(format!(
+                "local {} rate limit, next permit in {:?}",
+                platform, wait
+            ))),
+        }
+    }
+
+    /// 用该限速器包装一个客户端
+    pub fn wrap<T>(
+        self: &Arc<Self>,
+        inner: T,
+        key: impl Into<String>,
+        mode: RateLimitMode,
+    ) -> RateLimited<T> {
+        RateLimited {
+            inner,
+            key: key.into(),
+            limiter: Arc::clone(self),
+            mode,
+        }
+    }
+}
+
+/// 限速包装，发送前先从限速器获取许可，其余行为与内部客户端一致
+pub struct RateLimited<T> {
+    inner: T,
+    /// API key 或其他能区分配额的标识
+    key: String,
+    limiter: Arc<RateLimiter>,
+    mode: RateLimitMode,
+}
+
+impl<T> RateLimited<T> {
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: BuildTx> RateLimited<T> {
+    async fn acquire(&self) -> Result<(), SendError> {
+        self.limiter
+            .acquire(
+                self.inner.platform(),
+                &self.key,
+                self.inner.default_tps(),
+                self.mode,
+            )
+            .await
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for RateLimited<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: SendTxEncoded + BuildTx> SendTxEncoded for RateLimited<T> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+        self.acquire().await?;
+        self.inner.send_tx_encoded(tx_base64).await
+    }
+
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
+        self.acquire().await?;
+        self.inner.send_tx_raw(tx_bytes).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: SendBundle + BuildTx + Sync + Send> SendBundle for RateLimited<T> {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        self.acquire().await?;
+        self.inner.send_bundle(txs).await
+    }
+}
+
+impl<T: BuildTx> BuildTx for RateLimited<T> {
+    fn get_tip_address(&self) -> Pubkey {
+        self.inner.get_tip_address()
+    }
+    fn get_min_tip_amount(&self) -> u64 {
+        self.inner.get_min_tip_amount()
+    }
+    fn platform(&self) -> PlatformName {
+        self.inner.platform()
+    }
+    fn tip_recvs(&self) -> Vec<Pubkey> {
+        self.inner.tip_recvs()
+    }
+    fn uses_tip_transfer(&self) -> bool {
+        self.inner.uses_tip_transfer()
+    }
+    fn default_tps(&self) -> Option<u64> {
+        self.inner.default_tps()
+    }
+}
+
+impl<T: BuildV0Tx + BuildTx> BuildV0Tx for RateLimited<T> {}
+
+#[tokio::test]
+async fn test_rate_limiter_modes() {
+    let limiter = RateLimiter::new();
+
+    // Jito 默认 1 TPS：第一次通过，第二次立即拒绝
+    let jito = limiter.acquire(PlatformName::Jito, "key-a", Some(1), RateLimitMode::Reject);
+    assert!(jito.await.is_ok());
+    assert!(matches!(
+        limiter
+            .acquire(PlatformName::Jito, "key-a", Some(1), RateLimitMode::Reject)
+            .await,
+        Err(SendError::RateLimited(_))
+    ));
+    // 不同 key 使用独立的桶
+    assert!(
+        limiter
+            .acquire(PlatformName::Jito, "key-b", Some(1), RateLimitMode::Reject)
+            .await
+            .is_ok()
+    );
+    // 没有默认 TPS 时不限速
+    for _ in 0..100 {
+        assert!(
+            limiter
+                .acquire(PlatformName::Harmonic, "", None, RateLimitMode::Reject)
+                .await
+                .is_ok()
+        );
+    }
+
+    // key 覆盖不会被之后的平台覆盖清掉
+    limiter.set_key_tps(PlatformName::Helius, "vip", 1);
+    // 覆盖为 20 TPS 后，等待模式下第 21 个许可约需 50ms
+    limiter.set_platform_tps(PlatformName::Helius, 20);
+    let vip = limiter.acquire(PlatformName::Helius, "vip", Some(6), RateLimitMode::Reject);
+    assert!(vip.await.is_ok());
+    assert!(
+        limiter
+            .acquire(PlatformName::Helius, "vip", Some(6), RateLimitMode::Reject)
+            .await
+            .is_err()
+    );
+    let started = Instant::now();
+    for _ in 0..21 {
+        limiter
+            .acquire(PlatformName::Helius, "k", Some(6), RateLimitMode::Wait)
+            .await
+            .unwrap();
+    }
+    assert!(started.elapsed() >= Duration::from_millis(40));
+}
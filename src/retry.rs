@@ -0,0 +1,354 @@
+//! 重试与重发策略
+//!
+//! 传输类错误按指数退避重试；平台接收后按固定间隔重发同一份已签名字节，
+//! 直到交易落地、blockhash / nonce 失效或到达调用方的截止时间。
+//! 即使调用方没有给出截止时间或停止条件，重发也不会超过 `BLOCKHASH_TTL`。
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::blockhash_cache::BlockhashSnapshot;
+use crate::confirmation::{Confirmation, ConfirmationTracker, TxStatus};
+use crate::constants::BLOCKHASH_TTL;
+use crate::error::SendError;
+use crate::nonce::fetch_nonce_account;
+use crate::platform_clients::{SendTxEncoded, SolTx, SubmitReceipt, TxEnvelope};
+
+/// 重试 / 重发配置
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 第一次退避时长，之后每次翻倍
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// 连续可重试错误的最大次数，超过后放弃
+    pub max_consecutive_errors: u32,
+    /// 平台接收后的重发间隔，`None` 表示接收后即停止
+    pub rebroadcast_interval: Option<Duration>,
+    /// 调用方截止时间
+    pub deadline: Option<Instant>,
+    /// 交易所用 blockhash 的快照，从 `fetched_at` 起算 `BLOCKHASH_TTL` 后视为过期；
+    /// `None` 时从开始发送起算
+    pub blockhash: Option<BlockhashSnapshot>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+            max_consecutive_errors: 5,
+            rebroadcast_interval: Some(Duration::from_millis(500)),
+            deadline: None,
+            blockhash: None,
+        }
+    }
+}
+
+/// 停止重发的原因
+#[derive(Debug, Clone)]
+pub enum StopReason {
+    /// 平台已接收且未配置重发
+    Accepted,
+    /// 交易已落地
+    Landed(Confirmation),
+    /// blockhash 过期（超过 `last_valid_block_height`）
+    Expired,
+    /// nonce 已被推进，交易不可能再落地
+    NonceAdvanced,
+    /// 到达截止时间
+    Deadline,
+    /// 不可重试的错误，或连续错误次数超限
+    Failed(SendError),
+}
+
+/// 重发结果
+#[derive(Debug, Clone)]
+pub struct RetryOutcome {
+    pub sig: Signature,
+    /// 实际发起的提交次数
+    pub attempts: u32,
+    /// 最后一次成功提交的回执
+    pub last_receipt: Option<SubmitReceipt>,
+    pub stop: StopReason,
+}
+
+/// 重发停止条件，每次提交前检查，返回 `Some` 即停止
+#[async_trait::async_trait]
+pub trait StopCondition: Send + Sync {
+    async fn check(&self, sig: &Signature) -> Option<StopReason>;
+}
+
+/// 依赖 tracker 的后台轮询（`ConfirmationTracker::spawn`），签名需已 `track`
+#[async_trait::async_trait]
+impl StopCondition for ConfirmationTracker {
+    async fn check(&self, sig: &Signature) -> Option<StopReason> {
+        let confirmation = self.status(sig)?;
+        match confirmation.status {
+            TxStatus::Expired => Some(StopReason::Expired),
+            ref status if status.is_landed() => Some(StopReason::Landed(confirmation)),
+            _ => None,
+        }
+    }
+}
+
+/// nonce 值变化即停止：说明该 nonce 已被某笔交易消耗
+pub struct NonceWatch {
+    pub rpc: Arc<RpcClient>,
+    pub account: Pubkey,
+    /// 交易中使用的 nonce 值
+    pub hash: Hash,
+}
+
+#[async_trait::async_trait]
+impl StopCondition for NonceWatch {
+    async fn check(&self, _sig: &Signature) -> Option<StopReason> {
+        match fetch_nonce_account(&self.rpc, &self.account).await {
+            Ok((_, current)) if current != self.hash => Some(StopReason::NonceAdvanced),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("[NonceWatch] read {} failed: {}", self.account, e);
+                None
+            }
+        }
+    }
+}
+
+/// 按策略发送并重发同一笔交易，适用于任意平台客户端
+pub async fn send_with_retry<S: SendTxEncoded + ?Sized>(
+    sender: &S,
+    tx: &SolTx,
+    policy: &RetryPolicy,
+    stop_conditions: &[&dyn StopCondition],
+) -> RetryOutcome {
+    let sig = tx.sig();
+    let mut outcome = RetryOutcome {
+        sig,
+        attempts: 0,
+        last_receipt: None,
+        stop: StopReason::Accepted,
+    };
+    let bytes = match tx.to_bytes() {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            outcome.stop = StopReason::Failed(e);
+            return outcome;
+        }
+    };
+
+    // 普通交易超过 blockhash 有效期后不可能落地；durable nonce 交易不会过期，
+    // 未设置截止时间时以同样时长兜底，避免停止条件失效时无限重发
+    let expires_at = policy
+        .blockhash
+        .map_or_else(Instant::now, |snapshot| snapshot.fetched_at)
+        + BLOCKHASH_TTL;
+    let is_nonce = tx.advance_nonce().is_some();
+    let deadline = policy.deadline.or(is_nonce.then_some(expires_at));
+    let stop_at = deadline.map_or(expires_at, |d| if is_nonce { d } else { d.min(expires_at) });
+
+    let mut backoff = policy.initial_backoff;
+    let mut consecutive_errors = 0;
+    loop {
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            outcome.stop = StopReason::Deadline;
+            return outcome;
+        }
+        if !is_nonce && Instant::now() >= expires_at {
+            info!(
+                "[retry] {} blockhash expired after {} attempts",
+                sig, outcome.attempts
+            );
+            outcome.stop = StopReason::Expired;
+            return outcome;
+        }
+        for condition in stop_conditions {
+            if let Some(reason) = condition.check(&sig).await {
+                info!(
+                    "[retry] {} stopped after {} attempts: {:?}",
+                    sig, outcome.attempts, reason
+                );
+                outcome.stop = reason;
+                return outcome;
+            }
+        }
+
+        outcome.attempts += 1;
+        let wait = match sender.send_tx_raw(&bytes).await {
+            Ok(receipt) => {
+                outcome.last_receipt = Some(receipt);
+                consecutive_errors = 0;
+                backoff = policy.initial_backoff;
+                match policy.rebroadcast_interval {
+                    Some(interval) => interval,
+                    None => {
+                        outcome.stop = StopReason::Accepted;
+                        return outcome;
+                    }
+                }
+            }
+            // 已接收过的交易，重发被拒（如重复提交）不影响结果，按重发间隔继续
+            Err(e) if outcome.last_receipt.is_some() && !e.is_retryable() => {
+                warn!("[retry] rebroadcast {} rejected: {}", sig, e);
+                policy.rebroadcast_interval.unwrap_or(backoff)
+            }
+            Err(e) if e.is_retryable() && consecutive_errors < policy.max_consecutive_errors => {
+                consecutive_errors += 1;
+                warn!(
+                    "[retry] {} attempt {} failed: {}, backoff {:?}",
+                    sig, outcome.attempts, e, backoff
+                );
+                let wait = backoff;
+                backoff = (backoff * 2).min(policy.max_backoff);
+                wait
+            }
+            Err(e) => {
+                outcome.stop = StopReason::Failed(e);
+                return outcome;
+            }
+        };
+
+        let wait = wait.min(stop_at.saturating_duration_since(Instant::now()));
+        tokio::time::sleep(wait).await;
+    }
+}
+
+impl<T: SendTxEncoded + Sync + Send + ?Sized> TxEnvelope<'_, T> {
+    /// 按策略发送并重发，见 `send_with_retry`
+    pub async fn send_with_retry(
+        &self,
+        policy: &RetryPolicy,
+        stop_conditions: &[&dyn StopCondition],
+    ) -> RetryOutcome {
+        send_with_retry(self.sender, self.inner_tx(), policy, stop_conditions).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_with_retry() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::platform_clients::PlatformName;
+
+    /// 前 `fail_first` 次返回传输错误，之后接收
+    struct Flaky {
+        calls: AtomicU32,
+        fail_first: u32,
+    }
+    #[async_trait::async_trait]
+    impl SendTxEncoded for Flaky {
+        async fn send_tx_encoded(&self, _tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.fail_first {
+                return Err(SendError::Transport("connection reset".to_string()));
+            }
+            Ok(SubmitReceipt {
+                platform: PlatformName::Jito,
+                endpoint: "mock".to_string(),
+                latency: Duration::ZERO,
+                http_status: Some(200),
+                server_id: None,
+                raw: None,
+            })
+        }
+    }
+
+    /// 第 `after` 次检查时报告落地
+    struct LandsAfter {
+        checks: AtomicU32,
+        after: u32,
+    }
+    #[async_trait::async_trait]
+    impl StopCondition for LandsAfter {
+        async fn check(&self, sig: &Signature) -> Option<StopReason> {
+            (self.checks.fetch_add(1, Ordering::SeqCst) >= self.after).then(|| {
+                StopReason::Landed(Confirmation {
+                    sig: *sig,
+                    platform: Some(PlatformName::Jito),
+                    status: TxStatus::Confirmed,
+                    slot: Some(1),
+                    land_delay: None,
+                })
+            })
+        }
+    }
+
+    let payer = Keypair::new();
+    let tx = SolTx::Legacy(Transaction::new_signed_with_payer(
+        &[solana_system_interface::instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            1,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    ));
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        rebroadcast_interval: Some(Duration::from_millis(5)),
+        ..Default::default()
+    };
+
+    // 两次传输错误后接收，再重发两次后落地
+    let sender = Flaky {
+        calls: AtomicU32::new(0),
+        fail_first: 2,
+    };
+    let lands = LandsAfter {
+        checks: AtomicU32::new(0),
+        after: 5,
+    };
+    let outcome = send_with_retry(&sender, &tx, &policy, &[&lands]).await;
+    assert!(matches!(outcome.stop, StopReason::Landed(_)));
+    assert_eq!(outcome.attempts, 5);
+    assert!(outcome.last_receipt.is_some());
+
+    // 连续错误超限
+    let sender = Flaky {
+        calls: AtomicU32::new(0),
+        fail_first: u32::MAX,
+    };
+    let outcome = send_with_retry(&sender, &tx, &policy, &[]).await;
+    assert!(matches!(
+        outcome.stop,
+        StopReason::Failed(SendError::Transport(_))
+    ));
+    assert_eq!(outcome.attempts, policy.max_consecutive_errors + 1);
+
+    // 截止时间
+    let sender = Flaky {
+        calls: AtomicU32::new(0),
+        fail_first: 0,
+    };
+    let policy = RetryPolicy {
+        deadline: Some(Instant::now() + Duration::from_millis(30)),
+        ..policy
+    };
+    let outcome = send_with_retry(&sender, &tx, &policy, &[]).await;
+    assert!(matches!(outcome.stop, StopReason::Deadline));
+    assert!(outcome.attempts >= 2);
+
+    // 没有截止时间和停止条件：blockhash 过期后停止，而不是无限重发
+    let policy = RetryPolicy {
+        deadline: None,
+        blockhash: Some(BlockhashSnapshot {
+            blockhash: Hash::default(),
+            last_valid_block_height: 0,
+            slot: 0,
+            fetched_at: Instant::now() - (BLOCKHASH_TTL - Duration::from_millis(30)),
+        }),
+        ..policy
+    };
+    let outcome = send_with_retry(&sender, &tx, &policy, &[]).await;
+    assert!(matches!(outcome.stop, StopReason::Expired));
+    assert!(outcome.attempts >= 2);
+}
@@ -0,0 +1,577 @@
+//! 交易模拟
+//!
+//! 通过 `simulateTransaction`（`replaceRecentBlockhash`、`sigVerify: false`）读取 `unitsConsumed`，
+//! 按实际消耗 × 余量重新组装交易，避免 `cu_limit` 写死导致多付或 CU 不足。
+//!
+//! `Preflight` 包装在发送前（或与发送并行）对已签名的原始字节做一次模拟，
+//! 模拟失败的交易不再发出，避免白付 tip 或浪费限速配额。
+
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+use base64::Engine;
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::{Value, json};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::transaction::{TransactionError, VersionedTransaction};
+use tokio::sync::broadcast;
+
+use crate::constants::{HTTP_CLIENT, JSON_RPC_URL};
+use crate::error::{BuildError, SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::{
+    BuildTx, BuildV0Tx, HashParam, PlatformName, SendBundle, SendTxEncoded, Sender, SolTx,
+    SubmitReceipt, TxEnvelope,
+};
+use crate::tip::TipStrategy;
+
+/// 单笔交易 CU 上限
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// 一次模拟的结果
+#[derive(Debug, Clone, Default)]
+pub struct SimulationResult {
+    /// RPC 返回的 `err`，执行成功时为 `None`
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    /// 将 `err` 解码为 `TransactionError`
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        serde_json::from_value(self.err.clone()?).ok()
+    }
+
+    /// 执行出错时转换为 `SendError::SimulationFailed`
+    pub fn to_send_error(&self) -> Option<SendError> {
+        let err = self.err.as_ref()?;
+        Some(SendError::SimulationFailed {
+            err: self.transaction_error(),
+            message: err.to_string(),
+            logs: self.logs.clone(),
+        })
+    }
+}
+
+/// 交易模拟器，按 CU 消耗自动设置 `cu_limit`
+pub struct Simulator {
+    rpc_url: String,
+    http_client: Arc<Client>,
+    /// `cu_limit = unitsConsumed × margin`
+    margin: f64,
+}
+
+impl Simulator {
+    /// 使用 `JSON_RPC_URL`，余量 1.1
+    pub fn new() -> Self {
+        Self::init_with(JSON_RPC_URL.clone(), 1.1)
+    }
+
+    pub fn init_with(rpc_url: impl Into<String>, margin: f64) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            http_client: Arc::clone(&HTTP_CLIENT),
+            margin,
+        }
+    }
+
+    pub fn set_margin(&mut self, margin: f64) {
+        self.margin = margin;
+    }
+
+    /// 模拟交易，blockhash 由 RPC 替换为最新值，不校验签名
+    pub async fn simulate(&self, tx: &SolTx) -> Result<SimulationResult, SendError> {
+        self.simulate_with(&tx.to_base64()?, false).await
+    }
+
+    /// 按原样模拟已签名的交易（校验签名、不替换 blockhash），用于发送前检查
+    pub async fn simulate_signed(&self, tx_base64: &str) -> Result<SimulationResult, SendError> {
+        self.simulate_with(tx_base64, true).await
+    }
+
+    async fn simulate_with(
+        &self,
+        tx_base64: &str,
+        exact: bool,
+    ) -> Result<SimulationResult, SendError> {
+        let res = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateTransaction",
+                "params": [
+                    tx_base64,
+                    {
+                        "encoding": "base64",
+                        "sigVerify": exact,
+                        "replaceRecentBlockhash": !exact,
+                        "commitment": "processed",
+                    }
+                ],
+            }))
+            .send()
+            .await;
+        let (_, body) = read_http_response(res).await?;
+        parse_simulation_result(&parse_json_rpc_result(&body)?)
+    }
+
+    /// 用该模拟器给客户端加上发送前模拟
+    pub fn wrap<T>(self: &Arc<Self>, inner: T, mode: PreflightMode) -> Preflight<T> {
+        let (reports, _) = broadcast::channel(1024);
+        Preflight {
+            inner,
+            simulator: Arc::clone(self),
+            mode,
+            reports,
+        }
+    }
+
+    /// 模拟交易并按余量计算 `cu_limit`，执行出错时返回 `BuildError::Simulation`
+    pub async fn compute_unit_limit(&self, tx: &SolTx) -> Result<u32, BuildError> {
+        let result = self
+            .simulate(tx)
+            .await
+            .map_err(|e| BuildError::Simulation {
+                message: e.to_string(),
+                logs: Vec::new(),
+            })?;
+        if let Some(err) = result.err {
+            return Err(BuildError::Simulation {
+                message: err.to_string(),
+                logs: result.logs,
+            });
+        }
+        let consumed = result
+            .units_consumed
+            .ok_or_else(|| BuildError::Simulation {
+                message: "missing unitsConsumed".to_string(),
+                logs: result.logs,
+            })?;
+        let limit = ((consumed as f64 * self.margin).ceil() as u64)
+            .clamp(1, MAX_COMPUTE_UNIT_LIMIT as u64) as u32;
+        info!(
+            "[Simulator] {} consumed {} CU, cu_limit {}",
+            tx.sig(),
+            consumed,
+            limit
+        );
+        Ok(limit)
+    }
+
+    /// 自动 CU 版本的 `BuildV0Tx::build_v0_tx`：先以最大 `cu_limit` 组装并模拟，
+    /// 再以模拟结果重新组装。两次组装参数一致，tip / nonce / memo 的指令布局不变
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build_v0_tx<'a, T>(
+        &self,
+        sender: &'a T,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        tip: &TipStrategy,
+        nonce: &HashParam,
+        cu_price: Option<u64>,
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> Result<TxEnvelope<'a, T>, BuildError>
+    where
+        T: BuildV0Tx + BuildTx + SendTxEncoded + Display + Send + Sync,
+    {
+        let probe = sender.build_v0_tx(
+            ixs,
+            signer,
+            tip,
+            nonce,
+            &(Some(MAX_COMPUTE_UNIT_LIMIT), cu_price),
+            address_lookup_tables,
+            memo.clone(),
+        )?;
+        let cu_limit = self.compute_unit_limit(probe.inner_tx()).await?;
+        sender.build_v0_tx(
+            ixs,
+            signer,
+            tip,
+            nonce,
+            &(Some(cu_limit), cu_price),
+            address_lookup_tables,
+            memo,
+        )
+    }
+
+    /// 对象安全版本，参数含义同 `build_v0_tx`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build<'a>(
+        &self,
+        sender: &'a dyn Sender,
+        ixs: &[Instruction],
+        signer: &Arc<Keypair>,
+        tip: &TipStrategy,
+        nonce: &HashParam,
+        cu_price: Option<u64>,
+        address_lookup_tables: &[AddressLookupTableAccount],
+        memo: Option<Vec<&str>>,
+    ) -> Result<TxEnvelope<'a, dyn Sender>, BuildError> {
+        let probe = sender.build(
+            ixs,
+            signer,
+            tip,
+            nonce,
+            &(Some(MAX_COMPUTE_UNIT_LIMIT), cu_price),
+            address_lookup_tables,
+            memo.clone(),
+        )?;
+        let cu_limit = self.compute_unit_limit(probe.inner_tx()).await?;
+        sender.build(
+            ixs,
+            signer,
+            tip,
+            nonce,
+            &(Some(cu_limit), cu_price),
+            address_lookup_tables,
+            memo,
+        )
+    }
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 发送前模拟的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreflightMode {
+    /// 先模拟，失败时返回 `SendError::SimulationFailed`，不发送
+    Gate,
+    /// 与发送并行，只报告模拟结果，不影响发送
+    Parallel,
+}
+
+/// 一次发送前模拟的结果
+#[derive(Debug, Clone)]
+pub struct PreflightReport {
+    pub platform: PlatformName,
+    pub sig: Option<Signature>,
+    pub result: Result<SimulationResult, SendError>,
+}
+
+/// 发送前模拟包装，其余行为与内部客户端一致。
+///
+/// 模拟请求本身失败（RPC 不可用等）时不拦截发送；bundle 中的交易逐笔独立模拟，
+/// 依赖前序交易状态的 bundle 不应使用 `Gate` 模式。
+pub struct Preflight<T> {
+    inner: T,
+    simulator: Arc<Simulator>,
+    mode: PreflightMode,
+    reports: broadcast::Sender<PreflightReport>,
+}
+
+impl<T> Preflight<T> {
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// 订阅每次模拟的结果
+    pub fn subscribe(&self) -> broadcast::Receiver<PreflightReport> {
+        self.reports.subscribe()
+    }
+}
+
+impl<T: BuildTx> Preflight<T> {
+    /// 模拟一笔交易，执行出错时返回 `SimulationFailed`
+    async fn check(&self, tx_base64: &str) -> Result<(), SendError> {
+        let result = self.simulator.simulate_signed(tx_base64).await;
+        let verdict = match &result {
+            Ok(sim) => match sim.to_send_error() {
+                Some(e) => Err(e),
+                None => Ok(()),
+            },
+            Err(e) => {
+                warn!(
+                    "[Preflight] {} simulate request failed: {}",
+                    self.inner.platform(),
+                    e
+                );
+                Ok(())
+            }
+        };
+        let _ = self.reports.send(PreflightReport {
+            platform: self.inner.platform(),
+            sig: signature_of(tx_base64),
+            result,
+        });
+        verdict
+    }
+
+    async fn check_all(&self, txs_base64: &[String]) -> Result<(), SendError> {
+        for tx_base64 in txs_base64 {
+            self.check(tx_base64).await?;
+        }
+        Ok(())
+    }
+
+    /// 按 `mode` 组合模拟与发送
+    async fn guarded<F>(&self, txs_base64: &[String], send: F) -> Result<SubmitReceipt, SendError>
+    where
+        F: Future<Output = Result<SubmitReceipt, SendError>>,
+    {
+        match self.mode {
+            PreflightMode::Gate => {
+                self.check_all(txs_base64).await?;
+                send.await
+            }
+            PreflightMode::Parallel => {
+                let (sim, sent) = tokio::join!(self.check_all(txs_base64), send);
+                if let Err(e) = sim {
+                    warn!(
+                        "[Preflight] {} sent a failing transaction: {}",
+                        self.inner.platform(),
+                        e
+                    );
+                }
+                sent
+            }
+        }
+    }
+}
+
+/// 从 wire 格式中取第一个签名
+fn signature_of(tx_base64: &str) -> Option<Signature> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(tx_base64)
+        .ok()?;
+    let tx: VersionedTransaction = bincode::deserialize(&bytes).ok()?;
+    tx.signatures.first().copied()
+}
+
+impl<T: Display> Display for Preflight<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: SendTxEncoded + BuildTx> SendTxEncoded for Preflight<T> {
+    async fn send_tx_encoded(&self, tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+        let txs = [tx_base64.to_string()];
+        self.guarded(&txs, self.inner.send_tx_encoded(tx_base64))
+            .await
+    }
+
+    async fn send_tx_raw(&self, tx_bytes: &[u8]) -> Result<SubmitReceipt, SendError> {
+        let txs = [base64::engine::general_purpose::STANDARD.encode(tx_bytes)];
+        self.guarded(&txs, self.inner.send_tx_raw(tx_bytes)).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: SendBundle + BuildTx + Sync + Send> SendBundle for Preflight<T> {
+    async fn send_bundle(&self, txs: &[SolTx]) -> Result<SubmitReceipt, SendError> {
+        let txs_base64 = txs
+            .iter()
+            .map(SolTx::to_base64)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.guarded(&txs_base64, self.inner.send_bundle(txs)).await
+    }
+}
+
+impl<T: BuildTx> BuildTx for Preflight<T> {
+    fn get_tip_address(&self) -> Pubkey {
+        self.inner.get_tip_address()
+    }
+    fn get_min_tip_amount(&self) -> u64 {
+        self.inner.get_min_tip_amount()
+    }
+    fn platform(&self) -> PlatformName {
+        self.inner.platform()
+    }
+    fn tip_recvs(&self) -> Vec<Pubkey> {
+        self.inner.tip_recvs()
+    }
+    fn uses_tip_transfer(&self) -> bool {
+        self.inner.uses_tip_transfer()
+    }
+    fn default_tps(&self) -> Option<u64> {
+        self.inner.default_tps()
+    }
+}
+
+impl<T: BuildV0Tx + BuildTx> BuildV0Tx for Preflight<T> {}
+
+/// 解析 `{"context": {...}, "value": {"err": null, "logs": [...], "unitsConsumed": 1}}`
+fn parse_simulation_result(result: &Value) -> Result<SimulationResult, SendError> {
+    let value = result
+        .get("value")
+        .filter(|v| v.is_object())
+        .ok_or_else(|| SendError::Parse(format!("missing value, raw: {}", result)))?;
+    Ok(SimulationResult {
+        err: value.get("err").filter(|e| !e.is_null()).cloned(),
+        logs: value["logs"]
+            .as_array()
+            .map(|logs| {
+                logs.iter()
+                    .filter_map(|l| l.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        units_consumed: value["unitsConsumed"].as_u64(),
+    })
+}
+
+#[tokio::test]
+async fn test_simulated_cu_limit() {
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signer::Signer;
+    use solana_system_interface::instruction::transfer;
+
+    use crate::platform_clients::Region;
+    use crate::platform_clients::jito::Jito;
+
+    let url = crate::test_utils::mock_json_rpc(|req| {
+        assert_eq!(req["method"], "simulateTransaction");
+        assert_eq!(req["params"][1]["sigVerify"], false);
+        json!({
+            "context": { "slot": 1 },
+            "value": { "err": null, "logs": ["Program log: ok"], "unitsConsumed": 10_000 }
+        })
+    })
+    .await;
+
+    let simulator = Simulator::init_with(url, 1.2);
+    let jito = Jito::init_with("", Region::Frankfurt);
+    let signer = Arc::new(Keypair::new());
+    let ix = transfer(&signer.pubkey(), &Pubkey::new_unique(), 1);
+    let envelope = simulator
+        .build_v0_tx(
+            &jito,
+            &[ix],
+            &signer,
+            &TipStrategy::Fixed(5_000),
+            &HashParam::Blockhash(Hash::default()),
+            Some(100),
+            &[],
+            Some(vec!["memo"]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(envelope.tx.cu_limit, Some(12_000));
+    assert_eq!(envelope.tx.cu_price, Some(100));
+    assert_eq!(envelope.tx.tip, Some(5_000));
+    let SolTx::V0(tx) = envelope.inner_tx() else {
+        panic!("expected v0 transaction");
+    };
+    let limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(12_000);
+    assert_eq!(
+        tx.message.instructions()[0].data,
+        limit_ix.data,
+        "cu limit instruction keeps its position"
+    );
+}
+
+#[tokio::test]
+async fn test_preflight_modes() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::InstructionError;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+    use solana_system_interface::instruction::transfer;
+
+    // 模拟结果恒为 Custom(1) 错误
+    let url = crate::test_utils::mock_json_rpc(|req| {
+        assert_eq!(req["params"][1]["sigVerify"], true);
+        json!({
+            "context": { "slot": 1 },
+            "value": {
+                "err": { "InstructionError": [0, { "Custom": 1 }] },
+                "logs": ["Program log: insufficient funds"],
+                "unitsConsumed": 300
+            }
+        })
+    })
+    .await;
+
+    struct Counting(AtomicU32);
+    impl BuildTx for Counting {
+        fn get_tip_address(&self) -> Pubkey {
+            Pubkey::new_from_array([7; 32])
+        }
+        fn get_min_tip_amount(&self) -> u64 {
+            0
+        }
+        fn platform(&self) -> PlatformName {
+            PlatformName::Jito
+        }
+        fn tip_recvs(&self) -> Vec<Pubkey> {
+            vec![self.get_tip_address()]
+        }
+    }
+    #[async_trait::async_trait]
+    impl SendTxEncoded for Counting {
+        async fn send_tx_encoded(&self, _tx_base64: &str) -> Result<SubmitReceipt, SendError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(SubmitReceipt {
+                platform: PlatformName::Jito,
+                endpoint: "mock".to_string(),
+                latency: Duration::ZERO,
+                http_status: Some(200),
+                server_id: None,
+                raw: None,
+            })
+        }
+    }
+
+    let payer = Keypair::new();
+    let tx = SolTx::Legacy(Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    ));
+    let simulator = Arc::new(Simulator::init_with(url, 1.1));
+
+    // Gate：模拟失败，不发送
+    let gated = simulator.wrap(Counting(AtomicU32::new(0)), PreflightMode::Gate);
+    let mut reports = gated.subscribe();
+    match gated.send_tx_raw(&tx.to_bytes().unwrap()).await {
+        Err(SendError::SimulationFailed { err, logs, .. }) => {
+            assert_eq!(
+                err,
+                Some(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(1)
+                ))
+            );
+            assert_eq!(logs.len(), 1);
+        }
+        other => panic!("expected SimulationFailed, got {:?}", other),
+    }
+    assert_eq!(gated.inner().0.load(Ordering::SeqCst), 0);
+    let report = reports.recv().await.unwrap();
+    assert_eq!(report.sig, Some(tx.sig()));
+
+    // Parallel：照常发送，只报告
+    let parallel = simulator.wrap(Counting(AtomicU32::new(0)), PreflightMode::Parallel);
+    let mut reports = parallel.subscribe();
+    assert!(
+        parallel
+            .send_tx_encoded(&tx.to_base64().unwrap())
+            .await
+            .is_ok()
+    );
+    assert_eq!(parallel.inner().0.load(Ordering::SeqCst), 1);
+    let report = reports.recv().await.unwrap();
+    assert!(report.result.unwrap().err.is_some());
+}
@@ -0,0 +1,59 @@
+//! 测试用的本地 JSON-RPC 服务
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// 在本地端口启动 JSON-RPC 服务，返回其 URL。
+/// `handler` 收到解析后的请求体，返回值作为响应的 `result`。
+pub(crate) async fn mock_json_rpc<F>(handler: F) -> String
+where
+    F: Fn(&Value) -> Value + Send + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let Some(body) = read_request_body(&mut stream).await else {
+                continue;
+            };
+            let request: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+            let body =
+                json!({ "jsonrpc": "2.0", "id": 1, "result": handler(&request) }).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    url
+}
+
+/// 读完整个请求（header + body），返回 body
+async fn read_request_body(stream: &mut tokio::net::TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let len = head
+                .lines()
+                .find_map(|l| {
+                    l.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .and_then(|v| v.trim().parse::<usize>().ok())
+                })
+                .unwrap_or(0);
+            if body.len() >= len {
+                return Some(body.to_string());
+            }
+        }
+    }
+}
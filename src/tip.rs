@@ -0,0 +1,298 @@
+//! 动态 tip
+//!
+//! `TipOracle` 按平台给出百分位 tip 建议，`TipStrategy` 决定组装交易时实际写入的 tip。
+//! 交易组装是同步的，因此 oracle 只返回缓存中的最新值，由各实现自行在后台刷新。
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::constants::HTTP_CLIENT;
+use crate::error::{SendError, read_http_response};
+use crate::platform_clients::PlatformName;
+
+/// Jito tip floor REST 接口
+pub const JITO_TIP_FLOOR_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+
+/// tip 百分位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipPercentile {
+    P25,
+    P50,
+    P75,
+    P95,
+    P99,
+}
+
+/// 一组百分位 tip 建议（lamports）
+#[derive(Debug, Clone, Copy)]
+pub struct TipSuggestion {
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p95: u64,
+    pub p99: u64,
+    /// 本地拿到该值的时间
+    pub fetched_at: Instant,
+}
+
+impl TipSuggestion {
+    pub fn get(&self, percentile: TipPercentile) -> u64 {
+        match percentile {
+            TipPercentile::P25 => self.p25,
+            TipPercentile::P50 => self.p50,
+            TipPercentile::P75 => self.p75,
+            TipPercentile::P95 => self.p95,
+            TipPercentile::P99 => self.p99,
+        }
+    }
+}
+
+/// tip 建议来源
+pub trait TipOracle: Send + Sync {
+    /// 某个平台最新的 tip 建议，没有数据（或不支持该平台）时返回 `None`
+    fn suggest(&self, platform: PlatformName) -> Option<TipSuggestion>;
+}
+
+/// 组装交易时的 tip 取值方式
+#[derive(Clone, Default)]
+pub enum TipStrategy {
+    /// 平台最低 tip（`get_min_tip_amount`）
+    #[default]
+    Min,
+    /// 固定值，`0` 表示不加 tip 指令
+    Fixed(u64),
+    /// 取 oracle 的某个百分位，超过 `cap` 时截断；不低于平台最低 tip，
+    /// oracle 没有数据时退回平台最低 tip
+    Percentile {
+        oracle: Arc<dyn TipOracle>,
+        percentile: TipPercentile,
+        cap: Option<u64>,
+    },
+}
+
+impl TipStrategy {
+    /// 例如 `TipStrategy::percentile(oracle, TipPercentile::P75, Some(1_000_000))`
+    pub fn percentile(
+        oracle: Arc<dyn TipOracle>,
+        percentile: TipPercentile,
+        cap: Option<u64>,
+    ) -> Self {
+        TipStrategy::Percentile {
+            oracle,
+            percentile,
+            cap,
+        }
+    }
+
+    /// 计算实际 tip（lamports）
+    pub fn resolve(&self, platform: PlatformName, min_tip: u64) -> u64 {
+        match self {
+            TipStrategy::Min => min_tip,
+            TipStrategy::Fixed(lamports) => *lamports,
+            TipStrategy::Percentile {
+                oracle,
+                percentile,
+                cap,
+            } => {
+                let Some(suggestion) = oracle.suggest(platform) else {
+                    warn!(
+                        "[TipStrategy] no tip suggestion for {}, fallback to min tip {}",
+                        platform, min_tip
+                    );
+                    return min_tip;
+                };
+                let tip = suggestion.get(*percentile);
+                cap.map_or(tip, |cap| tip.min(cap)).max(min_tip)
+            }
+        }
+    }
+}
+
+/// `None` 对应 `Min`，`Some(x)` 对应 `Fixed(x)`
+impl From<Option<u64>> for TipStrategy {
+    fn from(tip: Option<u64>) -> Self {
+        match tip {
+            Some(lamports) => TipStrategy::Fixed(lamports),
+            None => TipStrategy::Min,
+        }
+    }
+}
+
+impl From<u64> for TipStrategy {
+    fn from(lamports: u64) -> Self {
+        TipStrategy::Fixed(lamports)
+    }
+}
+
+impl fmt::Debug for TipStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TipStrategy::Min => write!(f, "Min"),
+            TipStrategy::Fixed(lamports) => write!(f, "Fixed({})", lamports),
+            TipStrategy::Percentile {
+                percentile, cap, ..
+            } => write!(f, "Percentile({:?}, cap: {:?})", percentile, cap),
+        }
+    }
+}
+
+/// Jito tip floor 轮询，drop 时停止轮询
+///
+/// 默认只对 Jito 给出建议，其他同样走 Jito 拍卖的平台可通过 `with_platforms` 加入。
+pub struct JitoTipFloor {
+    rx: watch::Receiver<Option<TipSuggestion>>,
+    platforms: Vec<PlatformName>,
+    /// 超过该时长未刷新的数据不再使用
+    stale_after: Duration,
+    task: JoinHandle<()>,
+}
+
+impl JitoTipFloor {
+    /// 轮询 `JITO_TIP_FLOOR_URL`
+    pub fn new(interval: Duration) -> Self {
+        Self::init_with(JITO_TIP_FLOOR_URL, interval)
+    }
+
+    /// 轮询指定地址，过期阈值为 3 个轮询间隔
+    pub fn init_with(url: impl Into<String>, interval: Duration) -> Self {
+        let url = url.into();
+        let http_client = Arc::clone(&HTTP_CLIENT);
+        let (tx, rx) = watch::channel(None);
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match fetch_tip_floor(&http_client, &url).await {
+                    Ok(suggestion) => {
+                        tx.send_replace(Some(suggestion));
+                    }
+                    Err(e) => warn!("[JitoTipFloor] poll {} failed: {}", url, e),
+                }
+            }
+        });
+
+        Self {
+            rx,
+            platforms: vec![PlatformName::Jito],
+            stale_after: interval * 3,
+            task,
+        }
+    }
+
+    /// 设置适用的平台
+    pub fn with_platforms(mut self, platforms: &[PlatformName]) -> Self {
+        self.platforms = platforms.to_vec();
+        self
+    }
+
+    /// 最近一次拿到的值（可能已过期）
+    pub fn latest(&self) -> Option<TipSuggestion> {
+        *self.rx.borrow()
+    }
+}
+
+impl TipOracle for JitoTipFloor {
+    fn suggest(&self, platform: PlatformName) -> Option<TipSuggestion> {
+        if !self.platforms.contains(&platform) {
+            return None;
+        }
+        self.latest()
+            .filter(|s| s.fetched_at.elapsed() <= self.stale_after)
+    }
+}
+
+impl Drop for JitoTipFloor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn fetch_tip_floor(http_client: &Client, url: &str) -> Result<TipSuggestion, SendError> {
+    let res = http_client.get(url).send().await;
+    let (_, body) = read_http_response(res).await?;
+    let suggestion = parse_tip_floor(&body)?;
+    info!(
+        "[JitoTipFloor] p50 {} p75 {} p95 {}",
+        suggestion.p50, suggestion.p75, suggestion.p95
+    );
+    Ok(suggestion)
+}
+
+/// 解析 tip floor 响应：`[{"landed_tips_25th_percentile": 1.0e-5, ...}]`，单位为 SOL
+fn parse_tip_floor(body: &str) -> Result<TipSuggestion, SendError> {
+    let json: Value = serde_json::from_str(body)
+        .map_err(|e| SendError::Parse(format!("{}, raw: {}", e, body)))?;
+    let entry = json
+        .get(0)
+        .ok_or_else(|| SendError::Parse(format!("empty tip floor, raw: {}", body)))?;
+    let lamports = |field: &str| {
+        entry[field]
+            .as_f64()
+            .map(|sol| (sol * 1_000_000_000.0).round() as u64)
+            .ok_or_else(|| SendError::Parse(format!("missing {}, raw: {}", field, body)))
+    };
+    Ok(TipSuggestion {
+        p25: lamports("landed_tips_25th_percentile")?,
+        p50: lamports("landed_tips_50th_percentile")?,
+        p75: lamports("landed_tips_75th_percentile")?,
+        p95: lamports("landed_tips_95th_percentile")?,
+        p99: lamports("landed_tips_99th_percentile")?,
+        fetched_at: Instant::now(),
+    })
+}
+
+#[test]
+fn test_tip_strategy() {
+    let body = r#"[{
+        "time": "2025-01-01T00:00:00Z",
+        "landed_tips_25th_percentile": 0.000001,
+        "landed_tips_50th_percentile": 0.00001,
+        "landed_tips_75th_percentile": 0.0001,
+        "landed_tips_95th_percentile": 0.001,
+        "landed_tips_99th_percentile": 0.01,
+        "ema_landed_tips_50th_percentile": 0.00001
+    }]"#;
+    let suggestion = parse_tip_floor(body).unwrap();
+    assert_eq!(suggestion.p25, 1_000);
+    assert_eq!(suggestion.p75, 100_000);
+    assert_eq!(suggestion.p99, 10_000_000);
+    assert!(parse_tip_floor("[]").is_err());
+
+    struct Fixed(TipSuggestion);
+    impl TipOracle for Fixed {
+        fn suggest(&self, platform: PlatformName) -> Option<TipSuggestion> {
+            (platform == PlatformName::Jito).then_some(self.0)
+        }
+    }
+    let oracle: Arc<dyn TipOracle> = Arc::new(Fixed(suggestion));
+
+    let p75 = TipStrategy::percentile(Arc::clone(&oracle), TipPercentile::P75, None);
+    assert_eq!(p75.resolve(PlatformName::Jito, 1_000), 100_000);
+    // 截断到 cap
+    let capped = TipStrategy::percentile(Arc::clone(&oracle), TipPercentile::P99, Some(50_000));
+    assert_eq!(capped.resolve(PlatformName::Jito, 1_000), 50_000);
+    // 不低于平台最低 tip
+    let p25 = TipStrategy::percentile(Arc::clone(&oracle), TipPercentile::P25, None);
+    assert_eq!(p25.resolve(PlatformName::Jito, 5_000), 5_000);
+    // 没有数据时退回最低 tip
+    assert_eq!(p75.resolve(PlatformName::Helius, 200_000), 200_000);
+
+    assert_eq!(
+        TipStrategy::from(None).resolve(PlatformName::Jito, 1_000),
+        1_000
+    );
+    assert_eq!(
+        TipStrategy::from(Some(0)).resolve(PlatformName::Jito, 1_000),
+        0
+    );
+}
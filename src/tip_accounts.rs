@@ -0,0 +1,235 @@
+//! 运行时刷新的 tip 账户
+//!
+//! 各平台的 `*_TIP_ACCOUNTS` 常量只作为兜底。为某个平台注册 `TipAccountProvider` 后，
+//! `BuildTx::get_tip_address` / `tip_recvs` 优先使用 provider 最近拉取到的列表，
+//! 平台侧轮换 tip 账户时无需重新发版。
+
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+use ahash::AHashMap;
+use log::{info, warn};
+use rand::seq::IndexedRandom;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::error::{SendError, parse_json_rpc_result, read_http_response};
+use crate::platform_clients::PlatformName;
+use crate::platform_clients::jito::Jito;
+
+/// 进程内全局注册表
+static PROVIDERS: LazyLock<Registry> = LazyLock::new(Registry::default);
+
+/// tip 账户列表的来源，其他平台实现该 trait 即可接入
+#[async_trait::async_trait]
+pub trait TipAccountSource: Send + Sync {
+    async fn fetch_tip_accounts(&self) -> Result<Vec<Pubkey>, SendError>;
+}
+
+/// 后台定时拉取 tip 账户，drop 时停止
+pub struct TipAccountProvider {
+    rx: watch::Receiver<Arc<Vec<Pubkey>>>,
+    task: JoinHandle<()>,
+}
+
+impl TipAccountProvider {
+    /// 立即拉取一次，之后每隔 `interval` 刷新；拉取失败或结果为空时保留上一次的列表
+    pub fn spawn(source: Arc<dyn TipAccountSource>, interval: Duration) -> Self {
+        let (tx, rx) = watch::channel(Arc::new(Vec::new()));
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match source.fetch_tip_accounts().await {
+                    Ok(accounts) if !accounts.is_empty() => {
+                        if **tx.borrow() != accounts {
+                            info!("[TipAccountProvider] {} tip accounts", accounts.len());
+                        }
+                        tx.send_replace(Arc::new(accounts));
+                    }
+                    Ok(_) => warn!("[TipAccountProvider] empty tip account list"),
+                    Err(e) => warn!("[TipAccountProvider] fetch failed: {}", e),
+                }
+            }
+        });
+        Self { rx, task }
+    }
+
+    /// 最近拉取到的列表，尚未成功拉取时为空
+    pub fn accounts(&self) -> Arc<Vec<Pubkey>> {
+        Arc::clone(&self.rx.borrow())
+    }
+
+    /// 等待第一次拉取成功
+    pub async fn wait_ready(&self) {
+        let mut rx = self.rx.clone();
+        let _ = rx.wait_for(|accounts| !accounts.is_empty()).await;
+    }
+}
+
+impl Drop for TipAccountProvider {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 已注册的 provider，按平台索引
+#[derive(Default)]
+struct Registry {
+    providers: RwLock<AHashMap<PlatformName, Arc<TipAccountProvider>>>,
+}
+
+impl Registry {
+    fn register(
+        &self,
+        platform: PlatformName,
+        provider: Arc<TipAccountProvider>,
+    ) -> Option<Arc<TipAccountProvider>> {
+        self.providers.write().unwrap().insert(platform, provider)
+    }
+
+    fn unregister(&self, platform: PlatformName) -> Option<Arc<TipAccountProvider>> {
+        self.providers.write().unwrap().remove(&platform)
+    }
+
+    /// 持有读锁期间以平台当前的列表调用 `f`，不复制列表
+    fn with_accounts<R>(
+        &self,
+        platform: PlatformName,
+        fallback: &[Pubkey],
+        f: impl FnOnce(&[Pubkey]) -> R,
+    ) -> R {
+        let providers = self.providers.read().unwrap();
+        let live = providers
+            .get(&platform)
+            .map(|provider| provider.rx.borrow());
+        match live.as_deref() {
+            Some(accounts) if !accounts.is_empty() => f(accounts),
+            _ => f(fallback),
+        }
+    }
+
+    fn tip_address(&self, platform: PlatformName, fallback: &[Pubkey]) -> Pubkey {
+        self.with_accounts(platform, fallback, |accounts| {
+            *accounts
+                .choose(&mut rand::rng())
+                .unwrap_or_else(|| panic!("{} has no tip account", platform))
+        })
+    }
+}
+
+/// 为平台注册 provider，返回被替换的旧 provider
+pub fn register_provider(
+    platform: PlatformName,
+    provider: Arc<TipAccountProvider>,
+) -> Option<Arc<TipAccountProvider>> {
+    PROVIDERS.register(platform, provider)
+}
+
+/// 取消注册，之后该平台回到常量列表
+pub fn unregister_provider(platform: PlatformName) -> Option<Arc<TipAccountProvider>> {
+    PROVIDERS.unregister(platform)
+}
+
+/// 平台当前的 tip 账户：已注册且拉取成功时用 provider 的列表，否则用 `fallback`
+pub fn tip_recvs(platform: PlatformName, fallback: &[Pubkey]) -> Vec<Pubkey> {
+    PROVIDERS.with_accounts(platform, fallback, <[Pubkey]>::to_vec)
+}
+
+/// 从平台当前的 tip 账户中随机取一个
+pub fn tip_address(platform: PlatformName, fallback: &[Pubkey]) -> Pubkey {
+    PROVIDERS.tip_address(platform, fallback)
+}
+
+/// Jito `getTipAccounts`
+#[async_trait::async_trait]
+impl TipAccountSource for Jito {
+    async fn fetch_tip_accounts(&self) -> Result<Vec<Pubkey>, SendError> {
+        let res = self
+            .http_client
+            .post(self.status_url("getTipAccounts"))
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getTipAccounts",
+                "params": [],
+            }))
+            .send()
+            .await;
+        let (_, body) = read_http_response(res).await?;
+        let result = parse_json_rpc_result(&body)?;
+        result
+            .as_array()
+            .ok_or_else(|| SendError::Parse(format!("expected array, raw: {}", body)))?
+            .iter()
+            .map(|account| {
+                account
+                    .as_str()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| SendError::Parse(format!("invalid tip account: {}", account)))
+            })
+            .collect()
+    }
+}
+
+impl Jito {
+    /// 启动 Jito tip 账户刷新并注册到 `PlatformName::Jito`
+    pub fn spawn_tip_account_refresh(&self, interval: Duration) -> Arc<TipAccountProvider> {
+        let provider = Arc::new(TipAccountProvider::spawn(Arc::new(self.clone()), interval));
+        register_provider(PlatformName::Jito, Arc::clone(&provider));
+        provider
+    }
+}
+
+#[tokio::test]
+async fn test_tip_account_provider() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// 第一次返回错误，之后返回固定列表
+    struct Rotating {
+        failed_once: AtomicBool,
+        accounts: Vec<Pubkey>,
+    }
+    #[async_trait::async_trait]
+    impl TipAccountSource for Rotating {
+        async fn fetch_tip_accounts(&self) -> Result<Vec<Pubkey>, SendError> {
+            if !self.failed_once.swap(true, Ordering::SeqCst) {
+                return Err(SendError::Transport("down".to_string()));
+            }
+            Ok(self.accounts.clone())
+        }
+    }
+
+    let fallback = [Pubkey::new_unique()];
+    let live = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    // 独立的注册表，不影响全局 provider
+    let registry = Registry::default();
+    let recvs = |registry: &Registry| {
+        registry.with_accounts(PlatformName::Stellium, &fallback, <[Pubkey]>::to_vec)
+    };
+
+    // 未注册时使用常量
+    assert_eq!(recvs(&registry), fallback);
+
+    let provider = Arc::new(TipAccountProvider::spawn(
+        Arc::new(Rotating {
+            failed_once: AtomicBool::new(false),
+            accounts: live.clone(),
+        }),
+        Duration::from_millis(10),
+    ));
+    registry.register(PlatformName::Stellium, Arc::clone(&provider));
+    provider.wait_ready().await;
+    assert_eq!(recvs(&registry), live);
+    assert!(live.contains(&registry.tip_address(PlatformName::Stellium, &fallback)));
+
+    registry.unregister(PlatformName::Stellium);
+    assert_eq!(
+        registry.tip_address(PlatformName::Stellium, &fallback),
+        fallback[0]
+    );
+}